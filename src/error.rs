@@ -8,6 +8,12 @@ pub enum ContractError {
 
     #[error("Unauthorized")]
     Unauthorized {},
+
+    // retry_after is a block height or a time in seconds, depending on the
+    // window the collection's rate limit is measured in
+    #[error("Rate limited: retry after {unit} {retry_after}")]
+    RateLimited { retry_after: u64, unit: String },
     // Add any other custom errors you like here.
     // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
 }
+
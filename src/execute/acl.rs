@@ -0,0 +1,94 @@
+use cosmwasm_std::{
+    DepsMut, Env, MessageInfo, Response, StdError, StdResult, Storage,
+};
+
+use crate::error::ContractError;
+use crate::state::{AclEntry, Principal, DOCUMENTS, DOCUMENT_ACLS, SHARED_DOCUMENTS};
+use crate::query::{check_document_permission, RequestContext};
+use crate::execute::events::permission_event;
+
+pub fn execute_set_document_acl(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    document_id: String,
+    entries: Vec<AclEntry>,
+) -> Result<Response, ContractError> {
+    let doc = DOCUMENTS.load(deps.storage, (collection.clone(), document_id.clone()))?;
+    
+    // Owner, admin or anyone granted "share" can manage the ACL
+    let request = RequestContext { data: None, time: Some(env.block.time) };
+    if !check_document_permission(deps.as_ref(), &collection, &document_id, &doc, &info.sender, "share", &request)? {
+        return Err(StdError::generic_err("Unauthorized: Must own document or have share permission").into());
+    }
+    
+    // Normalize address principals so the reverse index matches query lookups
+    let mut normalized = Vec::with_capacity(entries.len());
+    for entry in entries {
+        if entry.actions.is_empty() {
+            continue;
+        }
+        let principal = match entry.principal {
+            Principal::Address(addr) => Principal::Address(deps.api.addr_validate(&addr)?.to_string()),
+            Principal::Role(role) => Principal::Role(role),
+        };
+        normalized.push(AclEntry { principal, actions: entry.actions });
+    }
+    
+    clear_document_acl(deps.storage, &collection, &document_id)?;
+    
+    for entry in &normalized {
+        let index_key = (entry.principal.index_key(), collection.clone(), document_id.clone());
+        SHARED_DOCUMENTS.save(deps.storage, index_key, &entry.actions)?;
+    }
+    if !normalized.is_empty() {
+        DOCUMENT_ACLS.save(deps.storage, (collection.clone(), document_id.clone()), &normalized)?;
+    }
+    
+    Ok(Response::new()
+        .add_event(permission_event("document_acl", &collection, &info.sender)
+            .add_attribute("document", document_id.clone())
+            .add_attribute("entries", normalized.len().to_string()))
+        .add_attribute("action", "set_document_acl")
+        .add_attribute("collection", collection)
+        .add_attribute("document", document_id)
+        .add_attribute("entries", normalized.len().to_string()))
+}
+
+// Removes the ACL of a document along with its reverse index entries
+pub fn clear_document_acl(
+    storage: &mut dyn Storage,
+    collection: &str,
+    document_id: &str,
+) -> StdResult<()> {
+    let key = (collection.to_string(), document_id.to_string());
+    let entries = DOCUMENT_ACLS.may_load(storage, key.clone())?.unwrap_or_default();
+    
+    for entry in entries {
+        SHARED_DOCUMENTS.remove(storage, (entry.principal.index_key(), key.0.clone(), key.1.clone()));
+    }
+    DOCUMENT_ACLS.remove(storage, key);
+    
+    Ok(())
+}
+
+// Gives the target document the same ACL entries as the source
+pub fn copy_document_acl(
+    storage: &mut dyn Storage,
+    from: (&str, &str),
+    to: (&str, &str),
+) -> StdResult<()> {
+    let entries = match DOCUMENT_ACLS.may_load(storage, (from.0.to_string(), from.1.to_string()))? {
+        Some(entries) => entries,
+        None => return Ok(()),
+    };
+    
+    for entry in &entries {
+        let index_key = (entry.principal.index_key(), to.0.to_string(), to.1.to_string());
+        SHARED_DOCUMENTS.save(storage, index_key, &entry.actions)?;
+    }
+    DOCUMENT_ACLS.save(storage, (to.0.to_string(), to.1.to_string()), &entries)?;
+    
+    Ok(())
+}
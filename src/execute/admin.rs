@@ -0,0 +1,312 @@
+use cosmwasm_std::{
+    Addr, DepsMut, Env, MessageInfo, Response, StdError, Timestamp,
+};
+
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::execute::events::admin_event;
+use crate::state::{
+    AdminAction, AdminActionStatus, AdminProposal, AdminSet, ADMIN, ADMIN_ACTIONS, ADMIN_ACTION_COUNT, ADMIN_SET,
+    PENDING_ADMIN,
+};
+
+pub fn execute_propose_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_admin: String,
+    expires_at: Option<Timestamp>,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err("Only admin can propose a new admin").into());
+    }
+
+    if let Some(expires_at) = expires_at {
+        if expires_at <= env.block.time {
+            return Err(StdError::generic_err("Proposal expiry must be in the future").into());
+        }
+    }
+
+    // A new proposal replaces any pending one
+    let new_admin_addr = deps.api.addr_validate(&new_admin)?;
+    PENDING_ADMIN.save(deps.storage, &AdminProposal {
+        new_admin: new_admin_addr.clone(),
+        proposed_at: env.block.time,
+        expires_at,
+    })?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "propose_admin")
+        .add_event(admin_event("propose_admin", &info.sender)
+            .add_attribute("proposed_admin", new_admin_addr.to_string()))
+        .add_attribute("admin", info.sender)
+        .add_attribute("proposed_admin", new_admin_addr);
+    if let Some(expires_at) = expires_at {
+        response = response.add_attribute("expires_at", expires_at.seconds().to_string());
+    }
+    Ok(response)
+}
+
+pub fn execute_accept_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let proposal = PENDING_ADMIN.may_load(deps.storage)?
+        .ok_or_else(|| StdError::generic_err("No pending admin proposal"))?;
+
+    if info.sender != proposal.new_admin {
+        return Err(StdError::generic_err("Only the proposed admin can accept").into());
+    }
+    if let Some(expires_at) = proposal.expires_at {
+        if env.block.time >= expires_at {
+            return Err(StdError::generic_err("Admin proposal has expired").into());
+        }
+    }
+
+    let old_admin = ADMIN.may_load(deps.storage)?;
+    ADMIN.save(deps.storage, &proposal.new_admin)?;
+    PENDING_ADMIN.remove(deps.storage);
+    // A single admin replaces the admin set
+    ADMIN_SET.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_admin")
+        .add_event(admin_event("accept_admin", &info.sender))
+        .add_attribute("old_admin", old_admin.map(|a| a.to_string()).unwrap_or_default())
+        .add_attribute("new_admin", proposal.new_admin))
+}
+
+pub fn execute_cancel_admin_proposal(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err("Only admin can cancel an admin proposal").into());
+    }
+
+    let proposal = PENDING_ADMIN.may_load(deps.storage)?
+        .ok_or_else(|| StdError::generic_err("No pending admin proposal"))?;
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_admin_proposal")
+        .add_event(admin_event("cancel_admin_proposal", &info.sender)
+            .add_attribute("proposed_admin", proposal.new_admin.to_string()))
+        .add_attribute("proposed_admin", proposal.new_admin))
+}
+
+pub fn execute_renounce_admin(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err("Only admin can renounce the admin role").into());
+    }
+
+    // Admin-only actions become permanently unavailable
+    ADMIN.remove(deps.storage);
+    PENDING_ADMIN.remove(deps.storage);
+    ADMIN_SET.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "renounce_admin")
+        .add_event(admin_event("renounce_admin", &info.sender))
+        .add_attribute("old_admin", info.sender))
+}
+
+pub fn execute_set_admin_set(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    members: Vec<String>,
+    threshold: u32,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err("Only admin can set the admin set").into());
+    }
+
+    let mut member_addrs: Vec<Addr> = Vec::with_capacity(members.len());
+    for member in members {
+        let addr = deps.api.addr_validate(&member)?;
+        if !member_addrs.contains(&addr) {
+            member_addrs.push(addr);
+        }
+    }
+    if threshold == 0 || threshold as usize > member_addrs.len() {
+        return Err(StdError::generic_err("Threshold must be between 1 and the number of members").into());
+    }
+
+    // The contract becomes its own admin, reachable only through approved actions
+    ADMIN.save(deps.storage, &env.contract.address)?;
+    PENDING_ADMIN.remove(deps.storage);
+    ADMIN_SET.save(deps.storage, &AdminSet {
+        members: member_addrs.clone(),
+        threshold,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_admin_set")
+        .add_event(admin_event("set_admin_set", &info.sender)
+            .add_attribute("members", member_addrs.len().to_string())
+            .add_attribute("threshold", threshold.to_string()))
+        .add_attribute("members", member_addrs.len().to_string())
+        .add_attribute("threshold", threshold.to_string()))
+}
+
+pub fn execute_propose_admin_action(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+    expires_at: Option<Timestamp>,
+) -> Result<Response, ContractError> {
+    let admin_set = ADMIN_SET.may_load(deps.storage)?
+        .ok_or_else(|| StdError::generic_err("No admin set configured"))?;
+    if !admin_set.members.contains(&info.sender) {
+        return Err(StdError::generic_err("Only admin set members can propose admin actions").into());
+    }
+    if !msg.is_privileged() {
+        return Err(StdError::generic_err("Message is not a privileged action").into());
+    }
+    if let Some(expires_at) = expires_at {
+        if expires_at <= env.block.time {
+            return Err(StdError::generic_err("Action expiry must be in the future").into());
+        }
+    }
+
+    let id = ADMIN_ACTION_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    ADMIN_ACTION_COUNT.save(deps.storage, &id)?;
+
+    // Proposing counts as the proposer's approval
+    let action = AdminAction {
+        id,
+        proposer: info.sender.clone(),
+        msg,
+        approvals: vec![info.sender.clone()],
+        status: AdminActionStatus::Pending,
+        created_at: env.block.time,
+        expires_at,
+        executed_at: None,
+    };
+    ADMIN_ACTIONS.save(deps.storage, id, &action)?;
+
+    if admin_set.threshold == 1 {
+        let executed = run_admin_action(deps, env, action)?;
+        return Ok(executed
+            .add_event(admin_event("propose_action", &info.sender)
+                .add_attribute("action_id", id.to_string()))
+            .add_attribute("action_id", id.to_string())
+            .add_attribute("proposer", info.sender));
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "propose_admin_action")
+        .add_event(admin_event("propose_action", &info.sender)
+            .add_attribute("action_id", id.to_string()))
+        .add_attribute("action_id", id.to_string())
+        .add_attribute("proposer", info.sender))
+}
+
+pub fn execute_approve_admin_action(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action_id: u64,
+) -> Result<Response, ContractError> {
+    let admin_set = ADMIN_SET.may_load(deps.storage)?
+        .ok_or_else(|| StdError::generic_err("No admin set configured"))?;
+    if !admin_set.members.contains(&info.sender) {
+        return Err(StdError::generic_err("Only admin set members can approve admin actions").into());
+    }
+
+    let mut action = ADMIN_ACTIONS.load(deps.storage, action_id)?;
+    if action.status != AdminActionStatus::Pending {
+        return Err(StdError::generic_err("Admin action is no longer pending").into());
+    }
+    if let Some(expires_at) = action.expires_at {
+        if env.block.time >= expires_at {
+            return Err(StdError::generic_err("Admin action has expired").into());
+        }
+    }
+    if action.approvals.contains(&info.sender) {
+        return Err(StdError::generic_err("Member has already approved this action").into());
+    }
+    action.approvals.push(info.sender.clone());
+
+    // Only approvals from current members count, the set may have changed
+    let approvals = action.approvals.iter()
+        .filter(|approver| admin_set.members.contains(approver))
+        .count() as u32;
+
+    if approvals >= admin_set.threshold {
+        // The inner message's error reverts this approval as well
+        let executed = run_admin_action(deps, env, action)?;
+        return Ok(executed
+            .add_event(admin_event("approve_action", &info.sender)
+                .add_attribute("action_id", action_id.to_string()))
+            .add_attribute("action_id", action_id.to_string())
+            .add_attribute("approver", info.sender));
+    }
+
+    ADMIN_ACTIONS.save(deps.storage, action_id, &action)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "approve_admin_action")
+        .add_event(admin_event("approve_action", &info.sender)
+            .add_attribute("action_id", action_id.to_string()))
+        .add_attribute("action_id", action_id.to_string())
+        .add_attribute("approver", info.sender)
+        .add_attribute("approvals", approvals.to_string())
+        .add_attribute("threshold", admin_set.threshold.to_string()))
+}
+
+pub fn execute_cancel_admin_action(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    action_id: u64,
+) -> Result<Response, ContractError> {
+    let mut action = ADMIN_ACTIONS.load(deps.storage, action_id)?;
+    if action.proposer != info.sender {
+        return Err(StdError::generic_err("Only the proposer can cancel an admin action").into());
+    }
+    if action.status != AdminActionStatus::Pending {
+        return Err(StdError::generic_err("Admin action is no longer pending").into());
+    }
+
+    action.status = AdminActionStatus::Cancelled;
+    ADMIN_ACTIONS.save(deps.storage, action_id, &action)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_admin_action")
+        .add_event(admin_event("cancel_action", &info.sender)
+            .add_attribute("action_id", action_id.to_string()))
+        .add_attribute("action_id", action_id.to_string()))
+}
+
+// Runs an approved message with the contract, the acting admin, as sender
+fn run_admin_action(
+    deps: DepsMut,
+    env: Env,
+    mut action: AdminAction,
+) -> Result<Response, ContractError> {
+    action.status = AdminActionStatus::Executed;
+    action.executed_at = Some(env.block.time);
+    ADMIN_ACTIONS.save(deps.storage, action.id, &action)?;
+
+    let info = MessageInfo {
+        sender: env.contract.address.clone(),
+        funds: vec![],
+    };
+    let event = admin_event("execute_action", &info.sender)
+        .add_attribute("action_id", action.id.to_string());
+    let response = crate::execute::execute(deps, env, info, action.msg)?;
+    Ok(response.add_event(event))
+}
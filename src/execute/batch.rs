@@ -1,29 +1,37 @@
 use cosmwasm_std::{
-    DepsMut, Env, MessageInfo, Response, StdResult,
+    DepsMut, Env, MessageInfo, Response,
 };
 
-use crate::msg::WriteOperation;
+use crate::error::ContractError;
+use crate::msg::{WriteOperation, WriteType};
 use crate::execute::{set, update, delete};
+use crate::execute::pricing::Payment;
 
 pub fn execute_batch_write(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     operations: Vec<WriteOperation>,
-) -> StdResult<Response> {
+    payment: &mut Payment,
+) -> Result<Response, ContractError> {
+    let mut response = Response::new().add_attribute("action", "batch_write");
     for op in operations {
-        match op.operation {
+        let result = match op.operation {
             WriteType::Set { data } => {
-                set::execute_set(deps.branch(), env.clone(), info.clone(), op.collection, op.document, data)?;
+                set::execute_set(deps.branch(), env.clone(), info.clone(), op.collection, op.document, data, payment)?
             }
             WriteType::Update { data } => {
-                update::execute_update(deps.branch(), env.clone(), info.clone(), op.collection, op.document, data)?;
+                update::execute_update(deps.branch(), env.clone(), info.clone(), op.collection, op.document, data, payment)?
             }
             WriteType::Delete => {
-                delete::execute_delete(deps.branch(), env.clone(), info.clone(), op.collection, op.document)?;
+                delete::execute_delete(deps.branch(), env.clone(), info.clone(), op.collection, op.document)?
             }
-        }
+        };
+        // Keep deposit refunds, hook submessages and the per-operation events
+        response = response
+            .add_submessages(result.messages)
+            .add_events(result.events);
     }
     
-    Ok(Response::new().add_attribute("action", "batch_write"))
-} 
\ No newline at end of file
+    Ok(response)
+}
@@ -0,0 +1,190 @@
+use cosmwasm_std::{
+    to_json_binary, DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult,
+};
+use cw_storage_plus::Bound;
+use serde_json;
+
+use crate::error::ContractError;
+use crate::msg::BulkWriteResponse;
+use crate::state::{Document, WriteKind, COLLECTION_FIELD_RULES, DOCUMENTS};
+use crate::query::{check_document_permission, RequestContext};
+use crate::query::fields::{get_path, redact_with_rules, unwritable_field};
+use crate::execute::{delete, update};
+use crate::execute::update::merge_json;
+use crate::execute::pause::check_not_paused;
+use crate::execute::pricing::Payment;
+use crate::execute::rate_limit::consume_rate_limit;
+
+const DEFAULT_BULK_LIMIT: u32 = 50;
+const MAX_BULK_LIMIT: u32 = 200;
+
+// Filters are JSON objects of field path to value, e.g. {"author.name":"bob"}.
+// A document matches when every path holds exactly that value. Fields the
+// sender cannot read never match.
+fn parse_filter(filter: &str) -> StdResult<serde_json::Map<String, serde_json::Value>> {
+    match serde_json::from_str(filter) {
+        Ok(serde_json::Value::Object(map)) => Ok(map),
+        Ok(_) => Err(StdError::generic_err("Filter must be a JSON object")),
+        Err(e) => Err(StdError::generic_err(format!("Invalid filter JSON: {}", e))),
+    }
+}
+
+// Which documents of a collection a bulk call looks at
+pub struct BulkSelection {
+    pub collection: String,
+    pub filter: String,
+    pub limit: Option<u32>,
+    pub start_after: Option<String>,
+}
+
+enum BulkAction<'a> {
+    Delete,
+    Update { merge: &'a serde_json::Value },
+}
+
+// Scans at most `limit` documents after start_after and returns the ids the
+// sender may apply `action` to, plus where the next call should continue.
+// Permissions and field write rules are checked exactly as the
+// single-document write would, so one protected document doesn't fail the page.
+fn select_documents(
+    deps: &DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    selection: &BulkSelection,
+    action: BulkAction,
+) -> StdResult<(Vec<String>, BulkWriteResponse)> {
+    let collection = selection.collection.as_str();
+    let filter = parse_filter(&selection.filter)?;
+    let limit = selection.limit.unwrap_or(DEFAULT_BULK_LIMIT).min(MAX_BULK_LIMIT) as usize;
+    let field_rules = COLLECTION_FIELD_RULES.may_load(deps.storage, collection.to_string())?
+        .unwrap_or_default();
+
+    let start = selection.start_after.clone().map(Bound::exclusive);
+    let scanned: Vec<(String, Document)> = DOCUMENTS
+        .prefix(collection.to_string())
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut selected = Vec::new();
+    let mut matched = 0u32;
+    for (document_id, doc) in &scanned {
+        let mut visible = doc.clone();
+        redact_with_rules(deps.as_ref(), &field_rules, &mut visible, Some(&info.sender), env.block.time)?;
+        let value: serde_json::Value = serde_json::from_str(&visible.data)
+            .map_err(|e| StdError::generic_err(format!("Invalid document JSON: {}", e)))?;
+        if !filter.iter().all(|(path, expected)| get_path(&value, path) == Some(expected)) {
+            continue;
+        }
+        matched += 1;
+        // Documents the sender may not change are skipped, not fatal
+        let allowed = match action {
+            BulkAction::Delete => {
+                let request = RequestContext { data: None, time: Some(env.block.time) };
+                check_document_permission(deps.as_ref(), collection, document_id, doc, &info.sender, "delete", &request)?
+            }
+            BulkAction::Update { merge } => {
+                let existing: serde_json::Value = serde_json::from_str(&doc.data)
+                    .map_err(|e| StdError::generic_err(format!("Invalid document JSON: {}", e)))?;
+                let merged = merge_json(existing.clone(), merge.clone());
+                let merged_data = serde_json::to_string(&merged)
+                    .map_err(|e| StdError::generic_err(e.to_string()))?;
+                let request = RequestContext { data: Some(&merged_data), time: Some(env.block.time) };
+                check_document_permission(deps.as_ref(), collection, document_id, doc, &info.sender, "update", &request)?
+                    && unwritable_field(deps.as_ref(), collection, &info.sender, &doc.owner, Some(&existing), &merged, env.block.time)?
+                        .is_none()
+            }
+        };
+        if allowed {
+            selected.push(document_id.clone());
+        }
+    }
+
+    let next_start_after = if scanned.len() == limit {
+        scanned.last().map(|(id, _)| id.clone())
+    } else {
+        None
+    };
+    let summary = BulkWriteResponse {
+        scanned: scanned.len() as u32,
+        matched,
+        processed: selected.len() as u32,
+        next_start_after,
+    };
+    Ok((selected, summary))
+}
+
+pub fn execute_delete_where(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    selection: BulkSelection,
+) -> Result<Response, ContractError> {
+    check_not_paused(deps.storage, &selection.collection, WriteKind::Delete)?;
+    let (selected, summary) = select_documents(&deps, &env, &info, &selection, BulkAction::Delete)?;
+    let collection = selection.collection;
+    // A bulk call counts as one write against the rate limit
+    if !selected.is_empty() {
+        consume_rate_limit(&mut deps, &env, &collection, &info.sender)?;
+    }
+
+    let mut response = Response::new();
+    for document_id in selected {
+        let result = delete::delete_document(deps.branch(), env.clone(), info.clone(), collection.clone(), document_id, false)?;
+        // Keep deposit refunds, hook submessages and write events
+        response = response
+            .add_submessages(result.messages)
+            .add_events(result.events);
+    }
+
+    response = response
+        .set_data(to_json_binary(&summary)?)
+        .add_attribute("action", "delete_where")
+        .add_attribute("collection", collection)
+        .add_attribute("processed", summary.processed.to_string());
+    // Not set once the collection is exhausted
+    if let Some(next_start_after) = summary.next_start_after {
+        response = response.add_attribute("next_start_after", next_start_after);
+    }
+    Ok(response)
+}
+
+pub fn execute_update_where(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    selection: BulkSelection,
+    merge: String,
+    payment: &mut Payment,
+) -> Result<Response, ContractError> {
+    let merge_value: serde_json::Value = serde_json::from_str(&merge)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    check_not_paused(deps.storage, &selection.collection, WriteKind::Update)?;
+    let (selected, summary) = select_documents(&deps, &env, &info, &selection, BulkAction::Update { merge: &merge_value })?;
+    let collection = selection.collection;
+    if !selected.is_empty() {
+        consume_rate_limit(&mut deps, &env, &collection, &info.sender)?;
+    }
+
+    let mut response = Response::new();
+    for document_id in selected {
+        let result = update::update_document(
+            deps.branch(), env.clone(), info.clone(), collection.clone(), document_id, merge.clone(), payment, false,
+        )?;
+        // Keep hook submessages and write events
+        response = response
+            .add_submessages(result.messages)
+            .add_events(result.events);
+    }
+
+    response = response
+        .set_data(to_json_binary(&summary)?)
+        .add_attribute("action", "update_where")
+        .add_attribute("collection", collection)
+        .add_attribute("processed", summary.processed.to_string());
+    // Not set once the collection is exhausted
+    if let Some(next_start_after) = summary.next_start_after {
+        response = response.add_attribute("next_start_after", next_start_after);
+    }
+    Ok(response)
+}
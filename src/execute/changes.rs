@@ -0,0 +1,31 @@
+use cosmwasm_std::{
+    Env, StdResult, Storage,
+};
+
+use crate::state::{Change, ChangeOp, Document, CHANGES, CHANGE_SEQUENCE};
+
+// Appends a change to the feed and returns it. Called after every document
+// save or removal.
+pub fn record_change(
+    storage: &mut dyn Storage,
+    env: &Env,
+    collection: &str,
+    document_id: &str,
+    op: ChangeOp,
+    doc: Option<&Document>,
+) -> StdResult<Change> {
+    let sequence = CHANGE_SEQUENCE.may_load(storage)?.unwrap_or_default() + 1;
+    CHANGE_SEQUENCE.save(storage, &sequence)?;
+
+    let change = Change {
+        sequence,
+        collection: collection.to_string(),
+        document: document_id.to_string(),
+        op,
+        height: env.block.height,
+        time: env.block.time,
+        content_hash: doc.map(|doc| doc.content_hash()),
+    };
+    CHANGES.save(storage, sequence, &change)?;
+    Ok(change)
+}
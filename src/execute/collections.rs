@@ -0,0 +1,263 @@
+use cosmwasm_std::{
+    CosmosMsg, DepsMut, Empty, Env, Event, MessageInfo, Order, Response, StdError, StdResult, Storage,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use cw_storage_plus::{Bound, Map};
+
+use crate::error::ContractError;
+use crate::state::{
+    Change, ChangeOp, CollectionOp, CollectionOpKind, Document, PauseScope, ADMIN, COLLECTION_FIELD_RULES, COLLECTION_HOOKS, COLLECTION_LOCKS,
+    COLLECTION_OPS, COLLECTION_OWNERS, COLLECTION_PERMISSIONS, COLLECTION_PRICING, COLLECTION_QUOTAS,
+    COLLECTION_RATE_LIMITS, COLLECTION_RULES, COLLECTION_WORKFLOWS, DOCUMENTS, DOCUMENT_DEPOSITS, PAUSES,
+};
+use crate::execute::changes::record_change;
+use crate::execute::events::write_event;
+use crate::execute::acl::{clear_document_acl, copy_document_acl};
+use crate::execute::pricing::refund_deposit;
+use crate::execute::quota::{record_usage, update_usage};
+use crate::execute::signing::invalidate_signature_requests;
+
+const DEFAULT_OP_LIMIT: u32 = 100;
+const MAX_OP_LIMIT: u32 = 500;
+
+// Starting an operation is admin only. Once started, anyone can send the same
+// message again to process the next chunk until it completes. Documents moved
+// here emit write events but don't dispatch collection hooks.
+pub fn execute_collection_op(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    kind: CollectionOpKind,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_OP_LIMIT).min(MAX_OP_LIMIT) as usize;
+
+    let mut op = match COLLECTION_OPS.may_load(deps.storage, collection.clone())? {
+        Some(op) if op.kind == kind => op,
+        Some(_) => return Err(StdError::generic_err("A different operation is running on this collection").into()),
+        None => start_op(deps.branch(), &env, &info, &collection, kind)?,
+    };
+
+    let start = op.cursor.clone().map(Bound::exclusive);
+    let documents: Vec<(String, Document)> = DOCUMENTS
+        .prefix(collection.clone())
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut refunds: Vec<CosmosMsg> = Vec::new();
+    let mut events: Vec<Event> = Vec::new();
+    for (document_id, doc) in &documents {
+        match &op.kind {
+            CollectionOpKind::Drop => {
+                let (change, refund) = remove_document(deps.storage, &env, &collection, document_id, doc)?;
+                events.push(write_event(&change, &info.sender, None));
+                refunds.extend(refund);
+            }
+            CollectionOpKind::Rename { to } => {
+                let change = copy_document(deps.storage, &env, &collection, to, document_id, doc)?;
+                events.push(write_event(&change, &info.sender, Some(doc)));
+                // The deposit follows the document instead of being refunded
+                let from_key = (collection.clone(), document_id.clone());
+                if let Some(deposit) = DOCUMENT_DEPOSITS.may_load(deps.storage, from_key.clone())? {
+                    DOCUMENT_DEPOSITS.remove(deps.storage, from_key);
+                    DOCUMENT_DEPOSITS.save(deps.storage, (to.clone(), document_id.clone()), &deposit)?;
+                }
+                let (change, _) = remove_document(deps.storage, &env, &collection, document_id, doc)?;
+                events.push(write_event(&change, &info.sender, None));
+            }
+            CollectionOpKind::Copy { to } => {
+                let change = copy_document(deps.storage, &env, &collection, to, document_id, doc)?;
+                events.push(write_event(&change, &info.sender, Some(doc)));
+            }
+        }
+    }
+
+    op.processed += documents.len() as u64;
+    let done = documents.len() < limit;
+    if done {
+        finish_op(deps.storage, &collection, &op.kind)?;
+    } else {
+        op.cursor = documents.last().map(|(id, _)| id.clone());
+        COLLECTION_OPS.save(deps.storage, collection.clone(), &op)?;
+    }
+
+    Ok(Response::new()
+        .add_messages(refunds)
+        .add_events(events)
+        .add_attribute("action", "collection_op")
+        .add_attribute("collection", collection)
+        .add_attribute("op", op_name(&op.kind))
+        .add_attribute("processed", op.processed.to_string())
+        .add_attribute("done", done.to_string()))
+}
+
+fn op_name(kind: &CollectionOpKind) -> &'static str {
+    match kind {
+        CollectionOpKind::Drop => "drop",
+        CollectionOpKind::Rename { .. } => "rename",
+        CollectionOpKind::Copy { .. } => "copy",
+    }
+}
+
+fn start_op(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    collection: &str,
+    kind: CollectionOpKind,
+) -> StdResult<CollectionOp> {
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err("Only admin can drop, rename or copy collections"));
+    }
+    if COLLECTION_LOCKS.has(deps.storage, collection.to_string()) {
+        return Err(StdError::generic_err("Collection is locked by a running operation"));
+    }
+
+    if let CollectionOpKind::Rename { to } | CollectionOpKind::Copy { to } = &kind {
+        if to.is_empty() || to == collection {
+            return Err(StdError::generic_err("Target collection must be a different, non-empty name"));
+        }
+        if COLLECTION_LOCKS.has(deps.storage, to.clone()) {
+            return Err(StdError::generic_err("Target collection is locked by a running operation"));
+        }
+        let has_documents = DOCUMENTS
+            .prefix(to.clone())
+            .keys(deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_some();
+        if has_documents {
+            return Err(StdError::generic_err("Target collection already has documents"));
+        }
+        COLLECTION_LOCKS.save(deps.storage, to.clone(), &collection.to_string())?;
+    }
+    COLLECTION_LOCKS.save(deps.storage, collection.to_string(), &collection.to_string())?;
+
+    Ok(CollectionOp {
+        kind,
+        started_by: info.sender.clone(),
+        started_at: env.block.time,
+        cursor: None,
+        processed: 0,
+    })
+}
+
+// Removes a document and everything attached to it, returning the change and
+// its deposit refund
+fn remove_document(
+    storage: &mut dyn Storage,
+    env: &Env,
+    collection: &str,
+    document_id: &str,
+    doc: &Document,
+) -> StdResult<(Change, Option<CosmosMsg>)> {
+    record_usage(storage, collection, Some(doc), None)?;
+    DOCUMENTS.remove(storage, (collection.to_string(), document_id.to_string()))?;
+    clear_document_acl(storage, collection, document_id)?;
+    invalidate_signature_requests(storage, collection, document_id)?;
+    let change = record_change(storage, env, collection, document_id, ChangeOp::Delete, None)?;
+    Ok((change, refund_deposit(storage, collection, document_id)?))
+}
+
+// Copies are admin writes, so they count toward usage without quota checks
+fn copy_document(
+    storage: &mut dyn Storage,
+    env: &Env,
+    from: &str,
+    to: &str,
+    document_id: &str,
+    doc: &Document,
+) -> StdResult<Change> {
+    DOCUMENTS.save(storage, (to.to_string(), document_id.to_string()), doc)?;
+    copy_document_acl(storage, (from, document_id), (to, document_id))?;
+    let size = doc.data.len() as u64;
+    update_usage(storage, to, &doc.owner, |usage| {
+        usage.documents += 1;
+        usage.bytes += size;
+    })?;
+    record_change(storage, env, to, document_id, ChangeOp::Set, Some(doc))
+}
+
+// Applies the collection settings once every document has been processed.
+// Rate limit buckets are left to expire on their own.
+fn finish_op(storage: &mut dyn Storage, collection: &str, kind: &CollectionOpKind) -> StdResult<()> {
+    match kind {
+        CollectionOpKind::Drop => transfer_settings(storage, collection, None, true)?,
+        CollectionOpKind::Rename { to } => {
+            transfer_settings(storage, collection, Some(to), true)?;
+            COLLECTION_LOCKS.remove(storage, to.clone());
+        }
+        CollectionOpKind::Copy { to } => {
+            transfer_settings(storage, collection, Some(to), false)?;
+            COLLECTION_LOCKS.remove(storage, to.clone());
+        }
+    }
+    COLLECTION_LOCKS.remove(storage, collection.to_string());
+    COLLECTION_OPS.remove(storage, collection.to_string());
+    Ok(())
+}
+
+fn transfer_settings(
+    storage: &mut dyn Storage,
+    from: &str,
+    to: Option<&str>,
+    remove_source: bool,
+) -> StdResult<()> {
+    transfer_entry(storage, &COLLECTION_PERMISSIONS, from, to, remove_source)?;
+    transfer_entry(storage, &COLLECTION_WORKFLOWS, from, to, remove_source)?;
+    transfer_entry(storage, &COLLECTION_RULES, from, to, remove_source)?;
+    transfer_entry(storage, &COLLECTION_FIELD_RULES, from, to, remove_source)?;
+    transfer_entry(storage, &COLLECTION_PRICING, from, to, remove_source)?;
+    transfer_entry(storage, &COLLECTION_QUOTAS, from, to, remove_source)?;
+    transfer_entry(storage, &COLLECTION_RATE_LIMITS, from, to, remove_source)?;
+    transfer_entry(storage, &COLLECTION_HOOKS, from, to, remove_source)?;
+
+    let owners = COLLECTION_OWNERS
+        .prefix(from.to_string())
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for owner in owners {
+        if let Some(to) = to {
+            COLLECTION_OWNERS.save(storage, (to.to_string(), owner.clone()), &Empty {})?;
+        }
+        if remove_source {
+            COLLECTION_OWNERS.remove(storage, (from.to_string(), owner));
+        }
+    }
+
+    // A pause follows a renamed collection but is never copied
+    let pause_key = PauseScope::Collection(from.to_string()).key();
+    if remove_source {
+        if let Some(mut pause) = PAUSES.may_load(storage, pause_key.clone())? {
+            PAUSES.remove(storage, pause_key);
+            if let Some(to) = to {
+                pause.scope = PauseScope::Collection(to.to_string());
+                PAUSES.save(storage, pause.scope.key(), &pause)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn transfer_entry<T: Serialize + DeserializeOwned>(
+    storage: &mut dyn Storage,
+    map: &Map<String, T>,
+    from: &str,
+    to: Option<&str>,
+    remove_source: bool,
+) -> StdResult<()> {
+    let value = match map.may_load(storage, from.to_string())? {
+        Some(value) => value,
+        None => return Ok(()),
+    };
+    if let Some(to) = to {
+        map.save(storage, to.to_string(), &value)?;
+    }
+    if remove_source {
+        map.remove(storage, from.to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use cosmwasm_std::{
+    Addr, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult,
+};
+
+use crate::error::ContractError;
+use crate::state::{ADMIN, COLLECTION_OWNERS, ROLE_MANAGERS};
+use crate::execute::events::{permission_event, role_event};
+
+// Admin or a delegated manager of the role
+pub fn can_manage_role(
+    deps: Deps,
+    sender: &Addr,
+    role: &str,
+) -> StdResult<bool> {
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() == Some(sender) {
+        return Ok(true);
+    }
+    Ok(ROLE_MANAGERS.has(deps.storage, (role.to_string(), sender.clone())))
+}
+
+// Admin or a delegated owner of the collection
+pub fn can_manage_collection(
+    deps: Deps,
+    sender: &Addr,
+    collection: &str,
+) -> StdResult<bool> {
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() == Some(sender) {
+        return Ok(true);
+    }
+    Ok(COLLECTION_OWNERS.has(deps.storage, (collection.to_string(), sender.clone())))
+}
+
+pub fn execute_set_role_manager(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    role: String,
+    manager: String,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    // Only admin can delegate role management
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err("Only admin can set role managers").into());
+    }
+    
+    let manager_addr = deps.api.addr_validate(&manager)?;
+    let key = (role.clone(), manager_addr.clone());
+    if enabled {
+        ROLE_MANAGERS.save(deps.storage, key, &Empty {})?;
+    } else {
+        ROLE_MANAGERS.remove(deps.storage, key);
+    }
+    
+    Ok(Response::new()
+        .add_event(role_event("set_manager", &role, &info.sender)
+            .add_attribute("manager", manager_addr.to_string())
+            .add_attribute("enabled", enabled.to_string()))
+        .add_attribute("action", "set_role_manager")
+        .add_attribute("role", role)
+        .add_attribute("manager", manager_addr)
+        .add_attribute("enabled", enabled.to_string()))
+}
+
+pub fn execute_set_collection_owner(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    collection: String,
+    owner: String,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    // Only admin can delegate collection management
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err("Only admin can set collection owners").into());
+    }
+    
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let key = (collection.clone(), owner_addr.clone());
+    if enabled {
+        COLLECTION_OWNERS.save(deps.storage, key, &Empty {})?;
+    } else {
+        COLLECTION_OWNERS.remove(deps.storage, key);
+    }
+    
+    Ok(Response::new()
+        .add_event(permission_event("collection_owner", &collection, &info.sender)
+            .add_attribute("owner", owner_addr.to_string())
+            .add_attribute("enabled", enabled.to_string()))
+        .add_attribute("action", "set_collection_owner")
+        .add_attribute("collection", collection)
+        .add_attribute("owner", owner_addr)
+        .add_attribute("enabled", enabled.to_string()))
+}
@@ -1,36 +1,67 @@
 use cosmwasm_std::{
-    Addr, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    DepsMut, Env, MessageInfo, Response, StdError,
 };
 
-use crate::state::{DOCUMENTS, ADMIN};
-use crate::query::check_permission;
+use crate::error::ContractError;
+use crate::state::{ChangeOp, WriteKind, DOCUMENTS};
+use crate::query::{check_document_permission, RequestContext};
+use crate::execute::acl::clear_document_acl;
+use crate::execute::changes::record_change;
+use crate::execute::hooks::dispatch_hooks;
+use crate::execute::events::write_event;
+use crate::execute::pause::check_not_paused;
+use crate::execute::pricing::refund_deposit;
+use crate::execute::quota::record_usage;
+use crate::execute::rate_limit::consume_rate_limit;
+use crate::execute::signing::invalidate_signature_requests;
 
 pub fn execute_delete(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     collection: String,
     document_id: String,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
+    delete_document(deps, env, info, collection, document_id, true)
+}
+
+// `rate_limited` is false for bulk deletes, which count once per call
+pub fn delete_document(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    document_id: String,
+    rate_limited: bool,
+) -> Result<Response, ContractError> {
+    check_not_paused(deps.storage, &collection, WriteKind::Delete)?;
+    
     let key = (collection.clone(), document_id.clone());
     
     // Check if document exists
     let doc = DOCUMENTS.load(deps.storage, key.clone())?;
     
-    // Check if user owns document OR has delete permission for collection
-    let admin = ADMIN.load(deps.storage)?;
-    let owns_document = doc.owner == info.sender;
-    let is_admin = info.sender == admin;
-    let has_delete_permission = check_permission(deps.as_ref(), &collection, &info.sender, "delete")?;
-    
-    if !owns_document && !is_admin && !has_delete_permission {
-        return Err(StdError::generic_err("Unauthorized: Must own document or have delete permission"));
+    // Check if user owns document, is shared on it OR has delete permission for collection
+    let request = RequestContext { data: None, time: Some(env.block.time) };
+    if !check_document_permission(deps.as_ref(), &collection, &document_id, &doc, &info.sender, "delete", &request)? {
+        return Err(StdError::generic_err("Unauthorized: Must own document or have delete permission").into());
+    }
+    if rate_limited {
+        consume_rate_limit(&mut deps, &env, &collection, &info.sender)?;
     }
     
-    DOCUMENTS.remove(deps.storage, key);
+    record_usage(deps.storage, &collection, Some(&doc), None)?;
+    DOCUMENTS.remove(deps.storage, key)?;
+    clear_document_acl(deps.storage, &collection, &document_id)?;
+    invalidate_signature_requests(deps.storage, &collection, &document_id)?;
+    let refund = refund_deposit(deps.storage, &collection, &document_id)?;
+    let change = record_change(deps.storage, &env, &collection, &document_id, ChangeOp::Delete, None)?;
     
-    Ok(Response::new()
+    let response = Response::new()
+        .add_messages(refund)
         .add_attribute("action", "delete")
         .add_attribute("collection", collection)
-        .add_attribute("document", document_id))
+        .add_attribute("document", document_id)
+        .add_event(write_event(&change, &info.sender, None));
+    Ok(dispatch_hooks(deps.storage, &change, &info.sender, response)?)
 } 
\ No newline at end of file
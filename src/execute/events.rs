@@ -0,0 +1,60 @@
+use cosmwasm_std::{
+    Addr, Event,
+};
+
+use crate::state::{Change, Document};
+
+// Event types, the chain emits them prefixed with "wasm-". Attributes are
+// documented in the README and are part of the contract interface.
+pub const WRITE_EVENT: &str = "docustore_write";
+pub const PERMISSION_EVENT: &str = "docustore_permission";
+pub const ROLE_EVENT: &str = "docustore_role";
+pub const ADMIN_EVENT: &str = "docustore_admin";
+pub const HOOK_EVENT: &str = "docustore_hook";
+
+// One per document write. The version is the change feed sequence of the write.
+// Attribute values can't be empty, so deletes carry no hash.
+pub fn write_event(change: &Change, actor: &Addr, doc: Option<&Document>) -> Event {
+    let event = Event::new(WRITE_EVENT)
+        .add_attribute("collection", change.collection.clone())
+        .add_attribute("document", change.document.clone())
+        .add_attribute("op", change.op.as_str())
+        .add_attribute("actor", actor.to_string())
+        .add_attribute("version", change.sequence.to_string())
+        .add_attribute("size", doc.map(|doc| doc.data.len()).unwrap_or_default().to_string());
+    match &change.content_hash {
+        Some(hash) => event.add_attribute("hash", hash),
+        None => event,
+    }
+}
+
+// Changes to who can do what in a collection, kind names the setting
+pub fn permission_event(kind: &str, collection: &str, actor: &Addr) -> Event {
+    Event::new(PERMISSION_EVENT)
+        .add_attribute("kind", kind)
+        .add_attribute("collection", collection)
+        .add_attribute("actor", actor.to_string())
+}
+
+// Role grants, revocations and role configuration
+pub fn role_event(op: &str, role: &str, actor: &Addr) -> Event {
+    Event::new(ROLE_EVENT)
+        .add_attribute("op", op)
+        .add_attribute("role", role)
+        .add_attribute("actor", actor.to_string())
+}
+
+// Admin handovers, admin set configuration and admin set actions
+pub fn admin_event(op: &str, actor: &Addr) -> Event {
+    Event::new(ADMIN_EVENT)
+        .add_attribute("op", op)
+        .add_attribute("actor", actor.to_string())
+}
+
+// Hook configuration changes and dispatches, contract is the hook contract
+pub fn hook_event(op: &str, collection: &str, contract: &Addr) -> Event {
+    Event::new(HOOK_EVENT)
+        .add_attribute("op", op)
+        .add_attribute("collection", collection)
+        .add_attribute("contract", contract.to_string())
+}
@@ -0,0 +1,44 @@
+use cosmwasm_std::{
+    DepsMut, Env, MessageInfo, Response, StdError,
+};
+
+use crate::error::ContractError;
+use crate::state::{FieldRule, COLLECTION_FIELD_RULES};
+use crate::execute::delegation::can_manage_collection;
+use crate::execute::events::permission_event;
+
+pub fn execute_set_field_rules(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    collection: String,
+    rules: Vec<FieldRule>,
+) -> Result<Response, ContractError> {
+    // Admin or a delegated collection owner
+    if !can_manage_collection(deps.as_ref(), &info.sender, &collection)? {
+        return Err(StdError::generic_err("Only admin or a collection owner can set field rules").into());
+    }
+    
+    for rule in &rules {
+        if rule.path.is_empty() || rule.path.split('.').any(|segment| segment.is_empty()) {
+            return Err(StdError::generic_err(format!("Invalid field path: {}", rule.path)).into());
+        }
+        if let Some(read) = &rule.read {
+            read.validate(deps.api)?;
+        }
+        if let Some(write) = &rule.write {
+            write.validate(deps.api)?;
+        }
+    }
+    
+    if rules.is_empty() {
+        COLLECTION_FIELD_RULES.remove(deps.storage, collection.clone());
+    } else {
+        COLLECTION_FIELD_RULES.save(deps.storage, collection.clone(), &rules)?;
+    }
+    
+    Ok(Response::new()
+        .add_event(permission_event("field_rules", &collection, &info.sender))
+        .add_attribute("action", "set_field_rules")
+        .add_attribute("collection", collection))
+}
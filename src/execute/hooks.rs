@@ -0,0 +1,129 @@
+use cosmwasm_std::{
+    Addr, DepsMut, Env, Event, MessageInfo, Reply, Response, StdError, StdResult, Storage, SubMsg, SubMsgResult,
+};
+
+use crate::error::ContractError;
+use crate::msg::DocustoreHookMsg;
+use crate::execute::events::{hook_event, HOOK_EVENT};
+use crate::state::{Change, Hook, HookMode, ADMIN, COLLECTION_HOOKS, MAX_COLLECTION_HOOKS};
+
+// Reply id for best-effort hooks, only failures come back
+pub const HOOK_REPLY_ID: u64 = 1;
+
+// Notifies the hooks of a collection about a write. Hooks run after the write
+// has been saved, a required hook that fails reverts the whole transaction.
+// Only document writes dispatch hooks, collection drops, renames and copies
+// and imports do not.
+pub fn dispatch_hooks(
+    storage: &dyn Storage,
+    change: &Change,
+    actor: &Addr,
+    mut response: Response,
+) -> StdResult<Response> {
+    let hooks = COLLECTION_HOOKS.may_load(storage, change.collection.clone())?.unwrap_or_default();
+    for hook in hooks {
+        let msg = DocustoreHookMsg {
+            change: change.clone(),
+            actor: actor.clone(),
+        }.into_cosmos_msg(&hook.contract)?;
+        let submsg = match hook.mode {
+            HookMode::BestEffort => SubMsg::reply_on_error(msg, HOOK_REPLY_ID),
+            HookMode::Required => SubMsg::new(msg),
+        };
+        response = response
+            .add_submessage(submsg)
+            .add_event(hook_event("dispatch", &change.collection, &hook.contract)
+                .add_attribute("document", change.document.clone())
+                .add_attribute("version", change.sequence.to_string())
+                .add_attribute("mode", format!("{:?}", hook.mode)));
+    }
+    Ok(response)
+}
+
+// A best-effort hook failed, its changes are already rolled back and the
+// write stands
+pub fn hook_reply(
+    _deps: DepsMut,
+    _env: Env,
+    msg: Reply,
+) -> StdResult<Response> {
+    match msg.result {
+        SubMsgResult::Err(error) => Ok(Response::new()
+            .add_attribute("action", "hook_failed")
+            .add_event(Event::new(HOOK_EVENT)
+                .add_attribute("op", "failed")
+                .add_attribute("error", error.clone()))
+            .add_attribute("error", error)),
+        SubMsgResult::Ok(_) => Ok(Response::new()),
+    }
+}
+
+pub fn execute_add_hook(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    collection: String,
+    contract: String,
+    mode: HookMode,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err("Only admin can add hooks").into());
+    }
+    
+    let contract_addr = deps.api.addr_validate(&contract)?;
+    let mut hooks = COLLECTION_HOOKS.may_load(deps.storage, collection.clone())?.unwrap_or_default();
+    match hooks.iter_mut().find(|hook| hook.contract == contract_addr) {
+        Some(hook) => hook.mode = mode.clone(),
+        None => {
+            if hooks.len() >= MAX_COLLECTION_HOOKS {
+                return Err(StdError::generic_err(format!("A collection can have at most {} hooks", MAX_COLLECTION_HOOKS)).into());
+            }
+            hooks.push(Hook { contract: contract_addr.clone(), mode: mode.clone() });
+        }
+    }
+    COLLECTION_HOOKS.save(deps.storage, collection.clone(), &hooks)?;
+    
+    Ok(Response::new()
+        .add_attribute("action", "add_hook")
+        .add_event(hook_event("add", &collection, &contract_addr)
+            .add_attribute("mode", format!("{:?}", mode))
+            .add_attribute("actor", info.sender.to_string()))
+        .add_attribute("collection", collection)
+        .add_attribute("contract", contract_addr)
+        .add_attribute("mode", format!("{:?}", mode)))
+}
+
+pub fn execute_remove_hook(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    collection: String,
+    contract: String,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err("Only admin can remove hooks").into());
+    }
+    
+    let contract_addr = deps.api.addr_validate(&contract)?;
+    let mut hooks = COLLECTION_HOOKS.may_load(deps.storage, collection.clone())?.unwrap_or_default();
+    let count = hooks.len();
+    hooks.retain(|hook| hook.contract != contract_addr);
+    if hooks.len() == count {
+        return Err(StdError::generic_err("Contract is not a hook of this collection").into());
+    }
+    
+    if hooks.is_empty() {
+        COLLECTION_HOOKS.remove(deps.storage, collection.clone());
+    } else {
+        COLLECTION_HOOKS.save(deps.storage, collection.clone(), &hooks)?;
+    }
+    
+    Ok(Response::new()
+        .add_attribute("action", "remove_hook")
+        .add_event(hook_event("remove", &collection, &contract_addr)
+            .add_attribute("actor", info.sender.to_string()))
+        .add_attribute("collection", collection)
+        .add_attribute("contract", contract_addr))
+}
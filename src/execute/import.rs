@@ -0,0 +1,142 @@
+use cosmwasm_std::{
+    CosmosMsg, DepsMut, Env, Event, MessageInfo, Response, StdError,
+};
+
+use crate::error::ContractError;
+use crate::msg::{ExportRecord, EXPORT_VERSION};
+use crate::state::{
+    ChangeOp, ImportProgress, WriteKind, ADMIN, COLLECTION_LOCKS, COLLECTION_PERMISSIONS, DOCUMENTS, IMPORT_PROGRESS, ROLE_GRANTS, USER_ROLES,
+};
+use crate::execute::acl::clear_document_acl;
+use crate::execute::changes::record_change;
+use crate::execute::pause::check_not_paused;
+use crate::execute::pricing::refund_deposit;
+use crate::execute::signing::invalidate_signature_requests;
+use crate::execute::events::{permission_event, role_event, write_event};
+use crate::execute::permissions::add_role_member;
+use crate::execute::quota::update_usage;
+use crate::query::export::chain_checksum;
+
+const MAX_IMPORT_RECORDS: usize = 200;
+
+// Restores records exactly as exported, keeping original owners and
+// timestamps. Pricing, quotas and rate limits don't apply to imports, pauses
+// and collection locks do. Imported writes don't dispatch collection hooks.
+// Overwriting a document refunds its deposit to the original depositor.
+pub fn execute_import(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    version: u32,
+    records: Vec<ExportRecord>,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err("Only admin can import").into());
+    }
+    if version != EXPORT_VERSION {
+        return Err(StdError::generic_err(format!("Unsupported export version {}, expected {}", version, EXPORT_VERSION)).into());
+    }
+    if records.len() > MAX_IMPORT_RECORDS {
+        return Err(StdError::generic_err(format!("Import exceeds {} records per call", MAX_IMPORT_RECORDS)).into());
+    }
+
+    let mut progress = IMPORT_PROGRESS.may_load(deps.storage)?.unwrap_or_default();
+    let imported = records.len();
+    let mut events: Vec<Event> = Vec::new();
+    let mut refunds: Vec<CosmosMsg> = Vec::new();
+    for record in records {
+        progress.checksum = chain_checksum(&progress.checksum, &record)?;
+        progress.records += 1;
+
+        match record {
+            ExportRecord::CollectionPermissions { collection, permissions } => {
+                permissions.validate(deps.api)?;
+                if COLLECTION_LOCKS.has(deps.storage, collection.clone()) {
+                    return Err(StdError::generic_err(format!("Collection {} is locked by a running operation", collection)).into());
+                }
+                COLLECTION_PERMISSIONS.save(deps.storage, collection.clone(), &permissions)?;
+                events.push(permission_event("collection_permissions", &collection, &info.sender));
+            }
+            ExportRecord::UserRoles { user, grants } => {
+                let user = deps.api.addr_validate(user.as_str())?;
+                let mut roles = USER_ROLES.may_load(deps.storage, user.clone())?.unwrap_or_default();
+                for grant_info in grants {
+                    if !roles.contains(&grant_info.role) {
+                        roles.push(grant_info.role.clone());
+                        add_role_member(deps.storage, &grant_info.role, &user)?;
+                    }
+                    let expires_at = grant_info.grant.expires_at
+                        .map(|t| t.seconds().to_string())
+                        .unwrap_or_else(|| "never".to_string());
+                    events.push(role_event("grant", &grant_info.role, &info.sender)
+                        .add_attribute("user", user.to_string())
+                        .add_attribute("expires_at", expires_at));
+                    ROLE_GRANTS.save(deps.storage, (user.clone(), grant_info.role), &grant_info.grant)?;
+                }
+                USER_ROLES.save(deps.storage, user, &roles)?;
+            }
+            ExportRecord::Document { collection, id, document } => {
+                deps.api.addr_validate(document.owner.as_str())?;
+                check_not_paused(deps.storage, &collection, WriteKind::Set)?;
+                let key = (collection.clone(), id.clone());
+                // An overwritten document loses its sharing, pending signatures and deposit
+                if let Some(old) = DOCUMENTS.may_load(deps.storage, key.clone())? {
+                    clear_document_acl(deps.storage, &collection, &id)?;
+                    invalidate_signature_requests(deps.storage, &collection, &id)?;
+                    refunds.extend(refund_deposit(deps.storage, &collection, &id)?);
+                    let size = old.data.len() as u64;
+                    update_usage(deps.storage, &collection, &old.owner, |usage| {
+                        usage.documents = usage.documents.saturating_sub(1);
+                        usage.bytes = usage.bytes.saturating_sub(size);
+                    })?;
+                }
+                let size = document.data.len() as u64;
+                update_usage(deps.storage, &collection, &document.owner, |usage| {
+                    usage.documents += 1;
+                    usage.bytes += size;
+                })?;
+                DOCUMENTS.save(deps.storage, key, &document)?;
+                let change = record_change(deps.storage, &env, &collection, &id, ChangeOp::Set, Some(&document))?;
+                events.push(write_event(&change, &info.sender, Some(&document)));
+            }
+        }
+    }
+    IMPORT_PROGRESS.save(deps.storage, &progress)?;
+
+    Ok(Response::new()
+        .add_messages(refunds)
+        .add_events(events)
+        .add_attribute("action", "import")
+        .add_attribute("records", imported.to_string())
+        .add_attribute("records_total", progress.records.to_string())
+        .add_attribute("checksum", progress.checksum))
+}
+
+// Confirms the import matches the final page of the export and resets progress
+pub fn execute_finish_import(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    records: u64,
+    checksum: String,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err("Only admin can finish an import").into());
+    }
+
+    let progress: ImportProgress = IMPORT_PROGRESS.may_load(deps.storage)?.unwrap_or_default();
+    if progress.records != records || progress.checksum != checksum {
+        return Err(StdError::generic_err(format!(
+            "Import incomplete: imported {} records with checksum {}, expected {} with {}",
+            progress.records, progress.checksum, records, checksum
+        )).into());
+    }
+    IMPORT_PROGRESS.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "finish_import")
+        .add_attribute("records", records.to_string())
+        .add_attribute("checksum", checksum))
+}
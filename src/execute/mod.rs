@@ -1,47 +1,215 @@
 use cosmwasm_std::{
-    Addr, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    from_json, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
 };
-use serde_json;
 
-use crate::msg::{ExecuteMsg, WriteOperation, WriteType};
-use crate::state::{Document, CollectionPermissions, DOCUMENTS, ADMIN, COLLECTION_PERMISSIONS, USER_ROLES};
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::state::CollectionOpKind;
 
 pub mod set;
 pub mod update;
 pub mod delete;
 pub mod batch;
 pub mod permissions;
+pub mod signing;
+pub mod workflow;
+pub mod acl;
+pub mod rules;
+pub mod fields;
+pub mod delegation;
+pub mod admin;
+pub mod pricing;
+pub mod quota;
+pub mod rate_limit;
+pub mod pause;
+pub mod collections;
+pub mod bulk;
+pub mod import;
+pub mod changes;
+pub mod events;
+pub mod hooks;
 
 pub fn execute(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Set { collection, document, data } => {
-            set::execute_set(deps, env, info, collection, document, data)
-        }
-        ExecuteMsg::Update { collection, document, data } => {
-            update::execute_update(deps, env, info, collection, document, data)
-        }
         ExecuteMsg::Delete { collection, document } => {
             delete::execute_delete(deps, env, info, collection, document)
         }
-        ExecuteMsg::BatchWrite { operations } => {
-            batch::execute_batch_write(deps, env, info, operations)
+        ExecuteMsg::DeleteWhere { collection, filter, limit, start_after } => {
+            let selection = bulk::BulkSelection { collection, filter, limit, start_after };
+            bulk::execute_delete_where(deps, env, info, selection)
+        }
+        msg @ (ExecuteMsg::Set { .. }
+            | ExecuteMsg::Update { .. }
+            | ExecuteMsg::UpdateWhere { .. }
+            | ExecuteMsg::BatchWrite { .. }) => {
+            let payment = pricing::Payment::from_funds(&info.funds);
+            execute_paid_write(deps, env, info, msg, payment)
+        }
+        ExecuteMsg::Receive(wrapper) => {
+            // The cw20 contract is the caller, the write is made for the token
+            // sender. Only the token every written collection is priced in can
+            // vouch for that sender.
+            let msg: ExecuteMsg = from_json(&wrapper.msg)?;
+            pricing::check_cw20_receive(deps.storage, &info.sender, wrapper.amount, &written_collections(&msg)?)?;
+            let payment = pricing::Payment::cw20(&info.sender, wrapper.amount);
+            let sender = deps.api.addr_validate(&wrapper.sender)?;
+            execute_paid_write(deps, env, MessageInfo { sender, funds: vec![] }, msg, payment)
         }
         ExecuteMsg::SetCollectionPermissions { collection, permissions } => {
             permissions::execute_set_permissions(deps, env, info, collection, permissions)
         }
-        ExecuteMsg::GrantRole { user, role } => {
-            permissions::execute_grant_role(deps, env, info, user, role)
+        ExecuteMsg::GrantRole { user, role, expires_at } => {
+            permissions::execute_grant_role(deps, env, info, user, role, expires_at)
         }
         ExecuteMsg::RevokeRole { user, role } => {
             permissions::execute_revoke_role(deps, env, info, user, role)
         }
-        ExecuteMsg::TransferAdmin { new_admin } => {
-            permissions::execute_transfer_admin(deps, env, info, new_admin)
+        ExecuteMsg::ProposeAdmin { new_admin, expires_at } => {
+            admin::execute_propose_admin(deps, env, info, new_admin, expires_at)
+        }
+        ExecuteMsg::AcceptAdmin {} => {
+            admin::execute_accept_admin(deps, env, info)
+        }
+        ExecuteMsg::CancelAdminProposal {} => {
+            admin::execute_cancel_admin_proposal(deps, env, info)
+        }
+        ExecuteMsg::RenounceAdmin {} => {
+            admin::execute_renounce_admin(deps, env, info)
+        }
+        ExecuteMsg::SetAdminSet { members, threshold } => {
+            admin::execute_set_admin_set(deps, env, info, members, threshold)
+        }
+        ExecuteMsg::ProposeAdminAction { msg, expires_at } => {
+            admin::execute_propose_admin_action(deps, env, info, *msg, expires_at)
+        }
+        ExecuteMsg::ApproveAdminAction { action_id } => {
+            admin::execute_approve_admin_action(deps, env, info, action_id)
+        }
+        ExecuteMsg::CancelAdminAction { action_id } => {
+            admin::execute_cancel_admin_action(deps, env, info, action_id)
+        }
+        ExecuteMsg::SetRoleInherits { role, inherits } => {
+            permissions::execute_set_role_inherits(deps, env, info, role, inherits)
+        }
+        ExecuteMsg::SetRoleManager { role, manager, enabled } => {
+            delegation::execute_set_role_manager(deps, env, info, role, manager, enabled)
+        }
+        ExecuteMsg::SetCollectionOwner { collection, owner, enabled } => {
+            delegation::execute_set_collection_owner(deps, env, info, collection, owner, enabled)
+        }
+        ExecuteMsg::PruneExpiredRoles { user } => {
+            permissions::execute_prune_expired_roles(deps, env, info, user)
+        }
+        ExecuteMsg::RequestSignatures { collection, document, signers, threshold, content_hash } => {
+            let request = signing::NewSignatureRequest { collection, document_id: document, signers, threshold, content_hash };
+            signing::execute_request_signatures(deps, env, info, request)
+        }
+        ExecuteMsg::RecordSignature { request_id, decision } => {
+            signing::execute_record_signature(deps, env, info, request_id, decision)
+        }
+        ExecuteMsg::SetCollectionWorkflow { collection, workflow } => {
+            workflow::execute_set_workflow(deps, env, info, collection, workflow)
+        }
+        ExecuteMsg::Transition { collection, document, to_state } => {
+            workflow::execute_transition(deps, env, info, collection, document, to_state)
+        }
+        ExecuteMsg::SetDocumentAcl { collection, document, entries } => {
+            acl::execute_set_document_acl(deps, env, info, collection, document, entries)
+        }
+        ExecuteMsg::SetCollectionRules { collection, rules } => {
+            rules::execute_set_rules(deps, env, info, collection, rules)
+        }
+        ExecuteMsg::SetFieldRules { collection, rules } => {
+            fields::execute_set_field_rules(deps, env, info, collection, rules)
+        }
+        ExecuteMsg::SetCollectionPricing { collection, pricing } => {
+            pricing::execute_set_pricing(deps, env, info, collection, pricing)
+        }
+        ExecuteMsg::SetAcceptedCw20 { contract, enabled } => {
+            pricing::execute_set_accepted_cw20(deps, env, info, contract, enabled)
+        }
+        ExecuteMsg::WithdrawFees { token, amount, recipient } => {
+            pricing::execute_withdraw_fees(deps, env, info, token, amount, recipient)
+        }
+        ExecuteMsg::SetCollectionQuota { collection, quota } => {
+            quota::execute_set_collection_quota(deps, env, info, collection, quota)
+        }
+        ExecuteMsg::SetGlobalQuota { quota } => {
+            quota::execute_set_global_quota(deps, env, info, quota)
+        }
+        ExecuteMsg::SetCollectionRateLimit { collection, limit } => {
+            rate_limit::execute_set_rate_limit(deps, env, info, collection, limit)
+        }
+        ExecuteMsg::AddHook { collection, contract, mode } => {
+            hooks::execute_add_hook(deps, env, info, collection, contract, mode)
+        }
+        ExecuteMsg::RemoveHook { collection, contract } => {
+            hooks::execute_remove_hook(deps, env, info, collection, contract)
+        }
+        ExecuteMsg::Pause { scope, reason } => {
+            pause::execute_pause(deps, env, info, scope, reason)
+        }
+        ExecuteMsg::Unpause { scope } => {
+            pause::execute_unpause(deps, env, info, scope)
+        }
+        ExecuteMsg::Import { version, records } => {
+            import::execute_import(deps, env, info, version, records)
+        }
+        ExecuteMsg::FinishImport { records, checksum } => {
+            import::execute_finish_import(deps, env, info, records, checksum)
+        }
+        ExecuteMsg::DropCollection { collection, limit } => {
+            collections::execute_collection_op(deps, env, info, collection, CollectionOpKind::Drop, limit)
+        }
+        ExecuteMsg::RenameCollection { from, to, limit } => {
+            collections::execute_collection_op(deps, env, info, from, CollectionOpKind::Rename { to }, limit)
+        }
+        ExecuteMsg::CopyCollection { from, to, limit } => {
+            collections::execute_collection_op(deps, env, info, from, CollectionOpKind::Copy { to }, limit)
         }
     }
+}
+
+// Collections a paid write touches
+fn written_collections(msg: &ExecuteMsg) -> StdResult<Vec<&str>> {
+    match msg {
+        ExecuteMsg::Set { collection, .. }
+        | ExecuteMsg::Update { collection, .. }
+        | ExecuteMsg::UpdateWhere { collection, .. } => Ok(vec![collection.as_str()]),
+        ExecuteMsg::BatchWrite { operations } => Ok(operations.iter().map(|op| op.collection.as_str()).collect()),
+        _ => Err(StdError::generic_err("Only Set, Update, UpdateWhere and BatchWrite accept payment")),
+    }
+}
+
+// Writes that charge collection pricing against the given payment
+fn execute_paid_write(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+    mut payment: pricing::Payment,
+) -> Result<Response, ContractError> {
+    let response = match msg {
+        ExecuteMsg::Set { collection, document, data } => {
+            set::execute_set(deps, env, info, collection, document, data, &mut payment)?
+        }
+        ExecuteMsg::Update { collection, document, data } => {
+            update::execute_update(deps, env, info, collection, document, data, &mut payment)?
+        }
+        ExecuteMsg::UpdateWhere { collection, filter, merge, limit, start_after } => {
+            let selection = bulk::BulkSelection { collection, filter, limit, start_after };
+            bulk::execute_update_where(deps, env, info, selection, merge, &mut payment)?
+        }
+        ExecuteMsg::BatchWrite { operations } => {
+            batch::execute_batch_write(deps, env, info, operations, &mut payment)?
+        }
+        _ => return Err(StdError::generic_err("Only Set, Update, UpdateWhere and BatchWrite accept payment").into()),
+    };
+    payment.settle()?;
+    Ok(response)
 } 
\ No newline at end of file
@@ -0,0 +1,81 @@
+use cosmwasm_std::{
+    DepsMut, Env, MessageInfo, Response, StdError, StdResult, Storage,
+};
+
+use crate::error::ContractError;
+use crate::state::{PauseInfo, PauseScope, WriteKind, ADMIN, ADMIN_SET, COLLECTION_LOCKS, PAUSES};
+
+const MAX_PAUSE_REASON_LEN: usize = 256;
+
+// Fails if any pause covers this write. Queries are never paused.
+pub fn check_not_paused(storage: &dyn Storage, collection: &str, kind: WriteKind) -> StdResult<()> {
+    let scopes = [
+        PauseScope::Contract,
+        PauseScope::Collection(collection.to_string()),
+        PauseScope::Write(kind),
+    ];
+    for scope in scopes {
+        if let Some(pause) = PAUSES.may_load(storage, scope.key())? {
+            let reason = pause.reason.map(|r| format!(": {}", r)).unwrap_or_default();
+            return Err(StdError::generic_err(format!("Paused ({}){}", scope.key(), reason)));
+        }
+    }
+    if let Some(source) = COLLECTION_LOCKS.may_load(storage, collection.to_string())? {
+        return Err(StdError::generic_err(format!("Collection is locked by a running operation on {}", source)));
+    }
+    Ok(())
+}
+
+pub fn execute_pause(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    scope: PauseScope,
+    reason: Option<String>,
+) -> Result<Response, ContractError> {
+    // Pausing is an emergency action, any admin set member can do it alone
+    let admin = ADMIN.may_load(deps.storage)?;
+    let is_member = ADMIN_SET.may_load(deps.storage)?
+        .is_some_and(|set| set.members.contains(&info.sender));
+    if admin.as_ref() != Some(&info.sender) && !is_member {
+        return Err(StdError::generic_err("Only admin can pause").into());
+    }
+    if reason.as_ref().is_some_and(|r| r.len() > MAX_PAUSE_REASON_LEN) {
+        return Err(StdError::generic_err(format!("Pause reason exceeds {} bytes", MAX_PAUSE_REASON_LEN)).into());
+    }
+
+    let key = scope.key();
+    PAUSES.save(deps.storage, key.clone(), &PauseInfo {
+        scope,
+        reason,
+        paused_by: info.sender.clone(),
+        paused_at: env.block.time,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "pause")
+        .add_attribute("scope", key)
+        .add_attribute("paused_by", info.sender))
+}
+
+pub fn execute_unpause(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    scope: PauseScope,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err("Only admin can unpause").into());
+    }
+
+    let key = scope.key();
+    if !PAUSES.has(deps.storage, key.clone()) {
+        return Err(StdError::generic_err(format!("Not paused: {}", key)).into());
+    }
+    PAUSES.remove(deps.storage, key.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "unpause")
+        .add_attribute("scope", key))
+}
@@ -1,8 +1,14 @@
 use cosmwasm_std::{
-    Addr, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    Addr, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult, Storage, Timestamp,
 };
 
-use crate::state::{CollectionPermissions, ADMIN, COLLECTION_PERMISSIONS, USER_ROLES};
+use crate::error::ContractError;
+use crate::state::{
+    CollectionPermissions, RoleGrant, ADMIN, COLLECTION_PERMISSIONS, MAX_INHERITED_ROLES, ROLE_GRANTS,
+    ROLE_INHERITS, ROLE_MEMBERS, ROLE_MEMBER_COUNTS, USER_ROLES,
+};
+use crate::execute::delegation::{can_manage_collection, can_manage_role};
+use crate::execute::events::{permission_event, role_event};
 
 pub fn execute_set_permissions(
     deps: DepsMut,
@@ -10,89 +16,238 @@ pub fn execute_set_permissions(
     info: MessageInfo,
     collection: String,
     permissions: CollectionPermissions,
-) -> StdResult<Response> {
-    // Only admin can set permissions
-    let admin = ADMIN.load(deps.storage)?;
-    if info.sender != admin {
-        return Err(StdError::generic_err("Only admin can set collection permissions"));
+) -> Result<Response, ContractError> {
+    // Admin or a delegated collection owner
+    if !can_manage_collection(deps.as_ref(), &info.sender, &collection)? {
+        return Err(StdError::generic_err("Only admin or a collection owner can set collection permissions").into());
     }
     
+    permissions.validate(deps.api)?;
+    
     COLLECTION_PERMISSIONS.save(deps.storage, collection.clone(), &permissions)?;
     
     Ok(Response::new()
+        .add_event(permission_event("collection_permissions", &collection, &info.sender))
         .add_attribute("action", "set_permissions")
         .add_attribute("collection", collection))
 }
 
 pub fn execute_grant_role(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     user: String,
     role: String,
-) -> StdResult<Response> {
-    // Only admin can grant roles
-    let admin = ADMIN.load(deps.storage)?;
-    if info.sender != admin {
-        return Err(StdError::generic_err("Only admin can grant roles"));
+    expires_at: Option<Timestamp>,
+) -> Result<Response, ContractError> {
+    // Admin or a delegated manager of this role
+    if !can_manage_role(deps.as_ref(), &info.sender, &role)? {
+        return Err(StdError::generic_err("Only admin or a role manager can grant this role").into());
+    }
+    
+    if let Some(expires_at) = expires_at {
+        if expires_at <= env.block.time {
+            return Err(StdError::generic_err("Role expiry must be in the future").into());
+        }
     }
     
     let user_addr = deps.api.addr_validate(&user)?;
+    prune_expired_roles(deps.storage, &user_addr, env.block.time)?;
     let mut user_roles = USER_ROLES.may_load(deps.storage, user_addr.clone())?.unwrap_or_default();
     
     if !user_roles.contains(&role) {
         user_roles.push(role.clone());
-        USER_ROLES.save(deps.storage, user_addr, &user_roles)?;
+        USER_ROLES.save(deps.storage, user_addr.clone(), &user_roles)?;
+        add_role_member(deps.storage, &role, &user_addr)?;
     }
     
+    // Granting again refreshes the grant details and expiry
+    let grant = RoleGrant {
+        granter: info.sender.clone(),
+        granted_at: env.block.time,
+        expires_at,
+    };
+    ROLE_GRANTS.save(deps.storage, (user_addr, role.clone()), &grant)?;
+    
+    let expires_at = expires_at.map(|t| t.seconds().to_string()).unwrap_or_else(|| "never".to_string());
+    
     Ok(Response::new()
+        .add_event(role_event("grant", &role, &info.sender)
+            .add_attribute("user", user.clone())
+            .add_attribute("expires_at", expires_at.clone()))
         .add_attribute("action", "grant_role")
         .add_attribute("user", user)
-        .add_attribute("role", role))
+        .add_attribute("role", role)
+        .add_attribute("expires_at", expires_at))
 }
 
 pub fn execute_revoke_role(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     user: String,
     role: String,
-) -> StdResult<Response> {
-    // Only admin can revoke roles
-    let admin = ADMIN.load(deps.storage)?;
-    if info.sender != admin {
-        return Err(StdError::generic_err("Only admin can revoke roles"));
+) -> Result<Response, ContractError> {
+    // Admin or a delegated manager of this role
+    if !can_manage_role(deps.as_ref(), &info.sender, &role)? {
+        return Err(StdError::generic_err("Only admin or a role manager can revoke this role").into());
     }
     
     let user_addr = deps.api.addr_validate(&user)?;
+    prune_expired_roles(deps.storage, &user_addr, env.block.time)?;
     let mut user_roles = USER_ROLES.may_load(deps.storage, user_addr.clone())?.unwrap_or_default();
     
-    user_roles.retain(|r| r != &role);
-    USER_ROLES.save(deps.storage, user_addr, &user_roles)?;
+    if user_roles.contains(&role) {
+        user_roles.retain(|r| r != &role);
+        USER_ROLES.save(deps.storage, user_addr.clone(), &user_roles)?;
+        remove_role_member(deps.storage, &role, &user_addr)?;
+    }
+    ROLE_GRANTS.remove(deps.storage, (user_addr, role.clone()));
     
     Ok(Response::new()
+        .add_event(role_event("revoke", &role, &info.sender)
+            .add_attribute("user", user.clone()))
         .add_attribute("action", "revoke_role")
         .add_attribute("user", user)
         .add_attribute("role", role))
 }
 
-pub fn execute_transfer_admin(
+pub fn execute_set_role_inherits(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    new_admin: String,
-) -> StdResult<Response> {
-    // Only current admin can transfer admin
-    let admin = ADMIN.load(deps.storage)?;
-    if info.sender != admin {
-        return Err(StdError::generic_err("Only admin can transfer admin role"));
+    role: String,
+    inherits: Vec<String>,
+) -> Result<Response, ContractError> {
+    // Only admin can define the role hierarchy
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err("Only admin can define role inheritance").into());
     }
     
-    let new_admin_addr = deps.api.addr_validate(&new_admin)?;
-    ADMIN.save(deps.storage, &new_admin_addr)?;
+    let mut inherits_unique: Vec<String> = Vec::with_capacity(inherits.len());
+    for parent in inherits {
+        if !inherits_unique.contains(&parent) {
+            inherits_unique.push(parent);
+        }
+    }
     
-    Ok(Response::new()
-        .add_attribute("action", "transfer_admin")
-        .add_attribute("old_admin", admin)
-        .add_attribute("new_admin", new_admin_addr))
-} 
\ No newline at end of file
+    // Walk everything reachable from the new parents, the role must not show up
+    let mut reachable = inherits_unique.clone();
+    let mut i = 0;
+    while i < reachable.len() {
+        if reachable[i] == role {
+            return Err(StdError::generic_err(format!("Role inheritance cycle through {}", role)).into());
+        }
+        if reachable.len() > MAX_INHERITED_ROLES {
+            return Err(StdError::generic_err(format!("Role {} inherits more than {} roles", role, MAX_INHERITED_ROLES)).into());
+        }
+        let parents = ROLE_INHERITS.may_load(deps.storage, reachable[i].clone())?
+            .unwrap_or_default();
+        for parent in parents {
+            if !reachable.contains(&parent) {
+                reachable.push(parent);
+            }
+        }
+        i += 1;
+    }
+    
+    if inherits_unique.is_empty() {
+        ROLE_INHERITS.remove(deps.storage, role.clone());
+    } else {
+        ROLE_INHERITS.save(deps.storage, role.clone(), &inherits_unique)?;
+    }
+    
+    let mut event = role_event("set_inherits", &role, &info.sender);
+    if !inherits_unique.is_empty() {
+        event = event.add_attribute("inherits", inherits_unique.join(","));
+    }
+    
+    let mut response = Response::new()
+        .add_event(event)
+        .add_attribute("action", "set_role_inherits")
+        .add_attribute("role", role);
+    if !inherits_unique.is_empty() {
+        response = response.add_attribute("inherits", inherits_unique.join(","));
+    }
+    Ok(response)
+}
+
+// Anyone can clean up a user's expired grants, they are ignored either way
+pub fn execute_prune_expired_roles(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    user: String,
+) -> Result<Response, ContractError> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let pruned = prune_expired_roles(deps.storage, &user_addr, env.block.time)?;
+    let events: Vec<_> = pruned.iter()
+        .map(|role| role_event("expire", role, &info.sender).add_attribute("user", user.clone()))
+        .collect();
+    
+    let mut response = Response::new()
+        .add_events(events)
+        .add_attribute("action", "prune_expired_roles")
+        .add_attribute("user", user);
+    if !pruned.is_empty() {
+        response = response.add_attribute("pruned", pruned.join(","));
+    }
+    Ok(response)
+}
+
+// Removes grants that expired at or before `now`, returning their roles
+pub fn prune_expired_roles(
+    storage: &mut dyn Storage,
+    user: &Addr,
+    now: Timestamp,
+) -> StdResult<Vec<String>> {
+    let mut user_roles = USER_ROLES.may_load(storage, user.clone())?.unwrap_or_default();
+    let mut pruned = Vec::new();
+    
+    for role in &user_roles {
+        let grant = ROLE_GRANTS.may_load(storage, (user.clone(), role.clone()))?;
+        if let Some(expires_at) = grant.and_then(|grant| grant.expires_at) {
+            if expires_at <= now {
+                pruned.push(role.clone());
+            }
+        }
+    }
+    
+    if !pruned.is_empty() {
+        user_roles.retain(|role| !pruned.contains(role));
+        USER_ROLES.save(storage, user.clone(), &user_roles)?;
+        for role in &pruned {
+            ROLE_GRANTS.remove(storage, (user.clone(), role.clone()));
+            remove_role_member(storage, role, user)?;
+        }
+    }
+    
+    Ok(pruned)
+}
+
+// Keeps ROLE_MEMBERS and ROLE_MEMBER_COUNTS in sync with USER_ROLES
+pub fn add_role_member(
+    storage: &mut dyn Storage,
+    role: &str,
+    user: &Addr,
+) -> StdResult<()> {
+    ROLE_MEMBERS.save(storage, (role.to_string(), user.clone()), &Empty {})?;
+    let count = ROLE_MEMBER_COUNTS.may_load(storage, role.to_string())?.unwrap_or_default();
+    ROLE_MEMBER_COUNTS.save(storage, role.to_string(), &(count + 1))
+}
+
+fn remove_role_member(
+    storage: &mut dyn Storage,
+    role: &str,
+    user: &Addr,
+) -> StdResult<()> {
+    ROLE_MEMBERS.remove(storage, (role.to_string(), user.clone()));
+    let count = ROLE_MEMBER_COUNTS.may_load(storage, role.to_string())?.unwrap_or_default();
+    if count <= 1 {
+        ROLE_MEMBER_COUNTS.remove(storage, role.to_string());
+        Ok(())
+    } else {
+        ROLE_MEMBER_COUNTS.save(storage, role.to_string(), &(count - 1))
+    }
+}
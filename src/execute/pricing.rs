@@ -0,0 +1,252 @@
+use cosmwasm_std::{
+    to_json_binary, Addr, BankMsg, Coin, CosmosMsg, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+    StdResult, Storage, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use crate::error::ContractError;
+use crate::state::{
+    CollectionPricing, Deposit, FeeBalance, TokenDenom, ACCEPTED_CW20, ADMIN, COLLECTED_FEES, COLLECTION_PRICING,
+    DOCUMENT_DEPOSITS,
+};
+use crate::execute::delegation::can_manage_collection;
+
+// Funds sent along with a write, either attached native coins or the amount
+// of a cw20 Receive. Every write charges against it and the remainder must
+// be zero once the message is done.
+pub struct Payment {
+    available: Vec<(TokenDenom, Uint128)>,
+}
+
+impl Payment {
+    pub fn from_funds(funds: &[Coin]) -> Self {
+        Self {
+            available: funds.iter()
+                .map(|coin| (TokenDenom::Native(coin.denom.clone()), coin.amount))
+                .collect(),
+        }
+    }
+
+    pub fn cw20(contract: &Addr, amount: Uint128) -> Self {
+        Self {
+            available: vec![(TokenDenom::Cw20(contract.to_string()), amount)],
+        }
+    }
+
+    fn charge(&mut self, token: &TokenDenom, amount: Uint128) -> StdResult<()> {
+        if amount.is_zero() {
+            return Ok(());
+        }
+        let available = self.available.iter_mut()
+            .find(|(denom, _)| denom == token)
+            .map(|(_, available)| available)
+            .filter(|available| **available >= amount)
+            .ok_or_else(|| StdError::generic_err(format!("Insufficient payment: requires {} {}", amount, token.key())))?;
+        *available -= amount;
+        Ok(())
+    }
+
+    // Overpaying is rejected rather than silently kept
+    pub fn settle(self) -> StdResult<()> {
+        if let Some((token, amount)) = self.available.iter().find(|(_, amount)| !amount.is_zero()) {
+            return Err(StdError::generic_err(format!("Payment exceeds price by {} {}", amount, token.key())));
+        }
+        Ok(())
+    }
+}
+
+// A cw20 Receive writes on behalf of the token sender, which only the token
+// contract itself can vouch for. Accept it only from an admin accepted cw20
+// token that every written collection is priced in, and only with an actual
+// payment.
+pub fn check_cw20_receive(
+    storage: &dyn Storage,
+    token: &Addr,
+    amount: Uint128,
+    collections: &[&str],
+) -> StdResult<()> {
+    if amount.is_zero() {
+        return Err(StdError::generic_err("Receive requires a non-zero amount"));
+    }
+    if !ACCEPTED_CW20.has(storage, token.clone()) {
+        return Err(StdError::generic_err(format!("Token {} is not an accepted cw20", token)));
+    }
+    let expected = TokenDenom::Cw20(token.to_string());
+    for collection in collections {
+        let pricing = COLLECTION_PRICING.may_load(storage, collection.to_string())?;
+        if pricing.map(|pricing| pricing.token) != Some(expected.clone()) {
+            return Err(StdError::generic_err(format!("Collection {} is not priced in token {}", collection, token)));
+        }
+    }
+    Ok(())
+}
+
+// Charges the collection's price for writing `data_len` bytes. Creating a
+// document also takes its deposit.
+pub fn charge_write(
+    storage: &mut dyn Storage,
+    payment: &mut Payment,
+    collection: &str,
+    document_id: &str,
+    payer: &Addr,
+    data_len: usize,
+    is_create: bool,
+) -> StdResult<()> {
+    let pricing = match COLLECTION_PRICING.may_load(storage, collection.to_string())? {
+        Some(pricing) => pricing,
+        None => return Ok(()),
+    };
+
+    let fee = pricing.write_fee + pricing.byte_fee.checked_mul(Uint128::from(data_len as u128))?;
+    if !fee.is_zero() {
+        payment.charge(&pricing.token, fee)?;
+        let key = pricing.token.key();
+        let mut balance = COLLECTED_FEES.may_load(storage, key.clone())?
+            .unwrap_or(FeeBalance { token: pricing.token.clone(), amount: Uint128::zero() });
+        balance.amount += fee;
+        COLLECTED_FEES.save(storage, key, &balance)?;
+    }
+
+    if is_create && !pricing.deposit.is_zero() {
+        payment.charge(&pricing.token, pricing.deposit)?;
+        DOCUMENT_DEPOSITS.save(storage, (collection.to_string(), document_id.to_string()), &Deposit {
+            depositor: payer.clone(),
+            token: pricing.token,
+            amount: pricing.deposit,
+        })?;
+    }
+
+    Ok(())
+}
+
+// Releases a document's deposit, returning the transfer back to the depositor
+pub fn refund_deposit(
+    storage: &mut dyn Storage,
+    collection: &str,
+    document_id: &str,
+) -> StdResult<Option<CosmosMsg>> {
+    let key = (collection.to_string(), document_id.to_string());
+    let deposit = match DOCUMENT_DEPOSITS.may_load(storage, key.clone())? {
+        Some(deposit) => deposit,
+        None => return Ok(None),
+    };
+    DOCUMENT_DEPOSITS.remove(storage, key);
+    transfer_msg(&deposit.token, &deposit.depositor, deposit.amount).map(Some)
+}
+
+pub fn transfer_msg(token: &TokenDenom, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    let msg = match token {
+        TokenDenom::Native(denom) => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin { denom: denom.clone(), amount }],
+        }.into(),
+        TokenDenom::Cw20(contract) => WasmMsg::Execute {
+            contract_addr: contract.clone(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }.into(),
+    };
+    Ok(msg)
+}
+
+pub fn execute_set_pricing(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    collection: String,
+    pricing: Option<CollectionPricing>,
+) -> Result<Response, ContractError> {
+    // Admin or a delegated collection owner
+    if !can_manage_collection(deps.as_ref(), &info.sender, &collection)? {
+        return Err(StdError::generic_err("Only admin or a collection owner can set collection pricing").into());
+    }
+
+    // Existing deposits keep the amount they were taken with
+    match pricing {
+        Some(pricing) => {
+            pricing.token.validate(deps.api)?;
+            // Collection owners set amounts, the admin picks the cw20 tokens
+            if let TokenDenom::Cw20(contract) = &pricing.token {
+                if !ACCEPTED_CW20.has(deps.storage, deps.api.addr_validate(contract)?) {
+                    return Err(StdError::generic_err(format!("Token {} is not an accepted cw20", contract)).into());
+                }
+            }
+            COLLECTION_PRICING.save(deps.storage, collection.clone(), &pricing)?;
+        }
+        None => COLLECTION_PRICING.remove(deps.storage, collection.clone()),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_collection_pricing")
+        .add_attribute("collection", collection))
+}
+
+pub fn execute_set_accepted_cw20(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    contract: String,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err("Only admin can set accepted cw20 tokens").into());
+    }
+
+    // Collections already priced in a removed token stop taking its Receive
+    let contract_addr = deps.api.addr_validate(&contract)?;
+    if enabled {
+        ACCEPTED_CW20.save(deps.storage, contract_addr.clone(), &Empty {})?;
+    } else {
+        ACCEPTED_CW20.remove(deps.storage, contract_addr.clone());
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_accepted_cw20")
+        .add_attribute("contract", contract_addr)
+        .add_attribute("enabled", enabled.to_string()))
+}
+
+pub fn execute_withdraw_fees(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    token: TokenDenom,
+    amount: Option<Uint128>,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err("Only admin can withdraw fees").into());
+    }
+
+    // Deposits are never part of the collected balance
+    let key = token.key();
+    let mut balance = COLLECTED_FEES.may_load(deps.storage, key.clone())?
+        .ok_or_else(|| StdError::generic_err("No fees collected for this token"))?;
+    let amount = amount.unwrap_or(balance.amount);
+    if amount.is_zero() || amount > balance.amount {
+        return Err(StdError::generic_err(format!("Amount must be between 1 and {}", balance.amount)).into());
+    }
+    balance.amount -= amount;
+    if balance.amount.is_zero() {
+        COLLECTED_FEES.remove(deps.storage, key);
+    } else {
+        COLLECTED_FEES.save(deps.storage, key, &balance)?;
+    }
+
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => info.sender,
+    };
+
+    Ok(Response::new()
+        .add_message(transfer_msg(&token, &recipient, amount)?)
+        .add_attribute("action", "withdraw_fees")
+        .add_attribute("token", token.key())
+        .add_attribute("amount", amount)
+        .add_attribute("recipient", recipient))
+}
@@ -0,0 +1,137 @@
+use cosmwasm_std::{
+    Addr, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Storage,
+};
+
+use crate::error::ContractError;
+use crate::state::{
+    Document, Quota, Usage, ADMIN, COLLECTION_QUOTAS, COLLECTION_USAGE, GLOBAL_QUOTA, USER_USAGE,
+};
+
+// Moves a document's usage from its previous owner to its new one and checks
+// the new owner's quotas. Called on every save and removal of a document.
+pub fn record_usage(
+    storage: &mut dyn Storage,
+    collection: &str,
+    old: Option<&Document>,
+    new: Option<&Document>,
+) -> StdResult<()> {
+    if let Some(old) = old {
+        update_usage(storage, collection, &old.owner, |usage| {
+            // Documents written before usage tracking aren't counted
+            usage.documents = usage.documents.saturating_sub(1);
+            usage.bytes = usage.bytes.saturating_sub(old.data.len() as u64);
+        })?;
+    }
+
+    let new = match new {
+        Some(new) => new,
+        None => return Ok(()),
+    };
+    let size = new.data.len() as u64;
+    let (usage, collection_usage) = update_usage(storage, collection, &new.owner, |usage| {
+        usage.documents += 1;
+        usage.bytes += size;
+    })?;
+
+    // Writes that shrink usage always go through, so owners over a lowered
+    // quota can still clean up
+    let grew = match old {
+        Some(old) => old.owner != new.owner || size > old.data.len() as u64,
+        None => true,
+    };
+    if !grew {
+        return Ok(());
+    }
+
+    if let Some(quota) = GLOBAL_QUOTA.may_load(storage)? {
+        check_quota(&quota, &usage, size, "global")?;
+    }
+    if let Some(quota) = COLLECTION_QUOTAS.may_load(storage, collection.to_string())? {
+        check_quota(&quota, &collection_usage, size, collection)?;
+    }
+    Ok(())
+}
+
+// Applies a change to an owner's usage counters without checking quotas
+pub fn update_usage(
+    storage: &mut dyn Storage,
+    collection: &str,
+    owner: &Addr,
+    apply: impl Fn(&mut Usage),
+) -> StdResult<(Usage, Usage)> {
+    let mut usage = USER_USAGE.may_load(storage, owner.clone())?.unwrap_or_default();
+    apply(&mut usage);
+    USER_USAGE.save(storage, owner.clone(), &usage)?;
+
+    let key = (owner.clone(), collection.to_string());
+    let mut collection_usage = COLLECTION_USAGE.may_load(storage, key.clone())?.unwrap_or_default();
+    apply(&mut collection_usage);
+    if collection_usage == Usage::default() {
+        COLLECTION_USAGE.remove(storage, key);
+    } else {
+        COLLECTION_USAGE.save(storage, key, &collection_usage)?;
+    }
+
+    Ok((usage, collection_usage))
+}
+
+fn check_quota(quota: &Quota, usage: &Usage, size: u64, scope: &str) -> StdResult<()> {
+    if let Some(max) = quota.max_document_size {
+        if size > max {
+            return Err(StdError::generic_err(format!("Quota exceeded ({}): document size {} exceeds {} bytes", scope, size, max)));
+        }
+    }
+    if let Some(max) = quota.max_documents {
+        if usage.documents > max {
+            return Err(StdError::generic_err(format!("Quota exceeded ({}): max {} documents per owner", scope, max)));
+        }
+    }
+    if let Some(max) = quota.max_bytes {
+        if usage.bytes > max {
+            return Err(StdError::generic_err(format!("Quota exceeded ({}): max {} bytes per owner", scope, max)));
+        }
+    }
+    Ok(())
+}
+
+pub fn execute_set_collection_quota(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    collection: String,
+    quota: Option<Quota>,
+) -> Result<Response, ContractError> {
+    // Only admin, collection owners could otherwise lock out writers
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err("Only admin can set collection quotas").into());
+    }
+
+    match quota {
+        Some(quota) => COLLECTION_QUOTAS.save(deps.storage, collection.clone(), &quota)?,
+        None => COLLECTION_QUOTAS.remove(deps.storage, collection.clone()),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_collection_quota")
+        .add_attribute("collection", collection))
+}
+
+pub fn execute_set_global_quota(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    quota: Option<Quota>,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err("Only admin can set the global quota").into());
+    }
+
+    match quota {
+        Some(quota) => GLOBAL_QUOTA.save(deps.storage, &quota)?,
+        None => GLOBAL_QUOTA.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "set_global_quota"))
+}
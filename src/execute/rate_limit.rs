@@ -0,0 +1,114 @@
+use cosmwasm_std::{
+    Addr, DepsMut, Env, MessageInfo, Response, StdError, Uint128,
+};
+
+use crate::error::ContractError;
+use crate::state::{RateBucket, RateLimit, ADMIN, COLLECTION_RATE_LIMITS, RATE_LIMIT_BUCKETS};
+use crate::query::has_active_role;
+
+// Largest window accepted, about a year in seconds, keeps bucket math small
+const MAX_RATE_WINDOW: u64 = 366 * 24 * 60 * 60;
+
+// Each write adds one window to full_at, the bucket holds max_writes windows
+pub struct BucketState {
+    pub now: Uint128,       // Scaled current time
+    pub full_at: Uint128,   // Scaled time the bucket is full again, before the next write
+    pub capacity: Uint128,
+    pub cost: Uint128,      // Scaled cost of one write
+}
+
+pub fn bucket_state(limit: &RateLimit, bucket: Option<RateBucket>, now: u64) -> BucketState {
+    let max_writes = Uint128::from(limit.max_writes);
+    let cost = Uint128::from(limit.window.length());
+    let now = Uint128::from(now) * max_writes;
+    let full_at = bucket
+        .filter(|b| b.max_writes == limit.max_writes && b.window == limit.window)
+        .map(|b| b.full_at.max(now))
+        .unwrap_or(now);
+    BucketState {
+        now,
+        full_at,
+        capacity: cost * max_writes,
+        cost,
+    }
+}
+
+// Earliest time, in the window's unit, at which one more write fits, rounded up
+pub fn retry_after(max_writes: u32, state: &BucketState) -> u64 {
+    let max_writes = Uint128::from(max_writes);
+    let full_at = state.full_at + state.cost;
+    let earliest = (full_at.saturating_sub(state.capacity) + max_writes - Uint128::one()) / max_writes;
+    earliest.u128() as u64
+}
+
+// Counts one write by sender against the collection's rate limit
+pub fn consume_rate_limit(
+    deps: &mut DepsMut,
+    env: &Env,
+    collection: &str,
+    sender: &Addr,
+) -> Result<(), ContractError> {
+    let limit = match COLLECTION_RATE_LIMITS.may_load(deps.storage, collection.to_string())? {
+        Some(limit) => limit,
+        None => return Ok(()),
+    };
+
+    if ADMIN.may_load(deps.storage)?.as_ref() == Some(sender) {
+        return Ok(());
+    }
+    for role in &limit.exempt_roles {
+        if has_active_role(deps.as_ref(), sender, role, Some(env.block.time))? {
+            return Ok(());
+        }
+    }
+
+    let key = (collection.to_string(), sender.clone());
+    let bucket = RATE_LIMIT_BUCKETS.may_load(deps.storage, key.clone())?;
+    let state = bucket_state(&limit, bucket, limit.window.now(env));
+    let full_at = state.full_at + state.cost;
+
+    if full_at - state.now > state.capacity {
+        return Err(ContractError::RateLimited {
+            retry_after: retry_after(limit.max_writes, &state),
+            unit: limit.window.unit().to_string(),
+        });
+    }
+
+    RATE_LIMIT_BUCKETS.save(deps.storage, key, &RateBucket {
+        full_at,
+        max_writes: limit.max_writes,
+        window: limit.window,
+    })?;
+    Ok(())
+}
+
+pub fn execute_set_rate_limit(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    collection: String,
+    limit: Option<RateLimit>,
+) -> Result<Response, ContractError> {
+    // Only admin, like quotas
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err("Only admin can set rate limits").into());
+    }
+
+    match limit {
+        Some(limit) => {
+            let window = limit.window.length();
+            if limit.max_writes == 0 || window == 0 || window > MAX_RATE_WINDOW {
+                return Err(StdError::generic_err(format!(
+                    "Rate limit needs at least one write and a window between 1 and {}", MAX_RATE_WINDOW
+                )).into());
+            }
+            COLLECTION_RATE_LIMITS.save(deps.storage, collection.clone(), &limit)?;
+        }
+        None => COLLECTION_RATE_LIMITS.remove(deps.storage, collection.clone()),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_rate_limit")
+        .add_attribute("collection", collection))
+}
@@ -0,0 +1,36 @@
+use cosmwasm_std::{
+    DepsMut, Env, MessageInfo, Response, StdError,
+};
+
+use crate::error::ContractError;
+use crate::rules::compile;
+use crate::state::COLLECTION_RULES;
+use crate::execute::delegation::can_manage_collection;
+use crate::execute::events::permission_event;
+
+pub fn execute_set_rules(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    collection: String,
+    rules: Option<String>,
+) -> Result<Response, ContractError> {
+    // Admin or a delegated collection owner
+    if !can_manage_collection(deps.as_ref(), &info.sender, &collection)? {
+        return Err(StdError::generic_err("Only admin or a collection owner can set collection rules").into());
+    }
+    
+    match rules {
+        Some(source) => {
+            // Parse and type check up front so evaluation never sees bad rules
+            let compiled = compile(&source)?;
+            COLLECTION_RULES.save(deps.storage, collection.clone(), &compiled)?;
+        }
+        None => COLLECTION_RULES.remove(deps.storage, collection.clone()),
+    }
+    
+    Ok(Response::new()
+        .add_event(permission_event("rules", &collection, &info.sender))
+        .add_attribute("action", "set_rules")
+        .add_attribute("collection", collection))
+}
@@ -1,41 +1,90 @@
 use cosmwasm_std::{
-    Addr, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    DepsMut, Env, MessageInfo, Response, StdError,
 };
 use serde_json;
 
-use crate::state::{Document, DOCUMENTS};
-use crate::query::check_permission;
+use crate::error::ContractError;
+use crate::state::{ChangeOp, Document, WriteKind, COLLECTION_WORKFLOWS, DOCUMENTS};
+use crate::query::{check_permission_with, check_state_permission, RequestContext};
+use crate::query::fields::check_field_writes;
+use crate::execute::acl::clear_document_acl;
+use crate::execute::changes::record_change;
+use crate::execute::hooks::dispatch_hooks;
+use crate::execute::events::write_event;
+use crate::execute::pause::check_not_paused;
+use crate::execute::pricing::{charge_write, Payment};
+use crate::execute::quota::record_usage;
+use crate::execute::rate_limit::consume_rate_limit;
+use crate::execute::signing::invalidate_signature_requests;
 
 pub fn execute_set(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     collection: String,
     document_id: String,
     data: String,
-) -> StdResult<Response> {
-    // Check create permission
-    if !check_permission(deps.as_ref(), &collection, &info.sender, "create")? {
-        return Err(StdError::generic_err("Insufficient permissions to create documents in this collection"));
+    payment: &mut Payment,
+) -> Result<Response, ContractError> {
+    check_not_paused(deps.storage, &collection, WriteKind::Set)?;
+    
+    // Check create permission, rules see the document being overwritten if any
+    let existing = DOCUMENTS.may_load(deps.storage, (collection.clone(), document_id.clone()))?;
+    let request = RequestContext { data: Some(&data), time: Some(env.block.time) };
+    if !check_permission_with(deps.as_ref(), &collection, &info.sender, "create", existing.as_ref(), &request)? {
+        return Err(StdError::generic_err("Insufficient permissions to create documents in this collection").into());
+    }
+    // A workflow state that restricts updates also guards overwrites
+    if let Some(doc) = &existing {
+        if check_state_permission(deps.as_ref(), &collection, doc, &info.sender, "update", request.time)? == Some(false) {
+            return Err(StdError::generic_err(format!(
+                "Documents in state {} cannot be overwritten", doc.status.as_deref().unwrap_or_default()
+            )).into());
+        }
     }
+    consume_rate_limit(&mut deps, &env, &collection, &info.sender)?;
     
     // Validate JSON
-    serde_json::from_str::<serde_json::Value>(&data)
+    let value = serde_json::from_str::<serde_json::Value>(&data)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    
+    // Overwriting counts as writing every protected field that changes
+    let previous = existing.as_ref()
+        .map(|doc| serde_json::from_str::<serde_json::Value>(&doc.data))
+        .transpose()
         .map_err(|e| StdError::generic_err(e.to_string()))?;
+    let owner = existing.as_ref().map_or(&info.sender, |doc| &doc.owner);
+    check_field_writes(deps.as_ref(), &collection, &info.sender, owner, previous.as_ref(), &value, env.block.time)?;
+    
+    charge_write(deps.storage, payment, &collection, &document_id, &info.sender, data.len(), existing.is_none())?;
+    
+    // New documents enter the workflow at its initial state
+    let status = COLLECTION_WORKFLOWS.may_load(deps.storage, collection.clone())?
+        .map(|workflow| workflow.initial_state);
     
     let doc = Document {
         data,
         owner: info.sender.clone(),
         created_at: env.block.time,
         updated_at: env.block.time,
+        status,
     };
     
+    record_usage(deps.storage, &collection, existing.as_ref(), Some(&doc))?;
     let key = (collection.clone(), document_id.clone());
     DOCUMENTS.save(deps.storage, key, &doc)?;
+    // An overwritten document loses its sharing and pending signatures
+    if existing.is_some() {
+        clear_document_acl(deps.storage, &collection, &document_id)?;
+    }
+    invalidate_signature_requests(deps.storage, &collection, &document_id)?;
+    let change = record_change(deps.storage, &env, &collection, &document_id, ChangeOp::Set, Some(&doc))?;
     
-    Ok(Response::new()
+    let response = Response::new()
         .add_attribute("action", "set")
         .add_attribute("collection", collection)
         .add_attribute("document", document_id)
-        .add_attribute("owner", info.sender))
+        .add_attribute("owner", info.sender.clone())
+        .add_event(write_event(&change, &info.sender, Some(&doc)));
+    Ok(dispatch_hooks(deps.storage, &change, &info.sender, response)?)
 } 
\ No newline at end of file
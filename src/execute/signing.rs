@@ -0,0 +1,184 @@
+use cosmwasm_std::{
+    Addr, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Storage,
+};
+
+use crate::error::ContractError;
+use crate::state::{
+    Signature, SignatureDecision, SignatureRequest, SignatureStatus, ADMIN, DOCUMENTS,
+    DOCUMENT_SIGNATURE_REQUESTS, SIGNATURE_REQUESTS, SIGNATURE_REQUEST_COUNT,
+};
+
+// Parameters of a RequestSignatures message
+pub struct NewSignatureRequest {
+    pub collection: String,
+    pub document_id: String,
+    pub signers: Vec<String>,
+    pub threshold: u32,
+    pub content_hash: Option<String>,  // Must match the current document when set
+}
+
+pub fn execute_request_signatures(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    request: NewSignatureRequest,
+) -> Result<Response, ContractError> {
+    let NewSignatureRequest { collection, document_id, signers, threshold, content_hash } = request;
+    let key = (collection.clone(), document_id.clone());
+    let doc = DOCUMENTS.load(deps.storage, key.clone())?;
+
+    // Only the document owner or admin can ask for signatures
+    let admin = ADMIN.may_load(deps.storage)?;
+    if doc.owner != info.sender && admin.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err("Unauthorized: Must own document to request signatures").into());
+    }
+
+    // Pin the request to the current version of the document
+    let current_hash = doc.content_hash();
+    if let Some(expected) = content_hash {
+        if expected != current_hash {
+            return Err(StdError::generic_err("Document content does not match the requested hash").into());
+        }
+    }
+
+    let mut signer_addrs: Vec<Addr> = Vec::with_capacity(signers.len());
+    for signer in signers {
+        let addr = deps.api.addr_validate(&signer)?;
+        if !signer_addrs.contains(&addr) {
+            signer_addrs.push(addr);
+        }
+    }
+
+    if threshold == 0 || threshold as usize > signer_addrs.len() {
+        return Err(StdError::generic_err("Threshold must be between 1 and the number of signers").into());
+    }
+
+    let id = SIGNATURE_REQUEST_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    SIGNATURE_REQUEST_COUNT.save(deps.storage, &id)?;
+
+    let request = SignatureRequest {
+        id,
+        collection: collection.clone(),
+        document: document_id.clone(),
+        content_hash: current_hash.clone(),
+        requester: info.sender.clone(),
+        signers: signer_addrs,
+        threshold,
+        signatures: vec![],
+        status: SignatureStatus::Pending,
+        created_at: env.block.time,
+        executed_at: None,
+    };
+    SIGNATURE_REQUESTS.save(deps.storage, id, &request)?;
+
+    let mut pending = DOCUMENT_SIGNATURE_REQUESTS.may_load(deps.storage, key.clone())?.unwrap_or_default();
+    pending.push(id);
+    DOCUMENT_SIGNATURE_REQUESTS.save(deps.storage, key, &pending)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "request_signatures")
+        .add_attribute("request_id", id.to_string())
+        .add_attribute("collection", collection)
+        .add_attribute("document", document_id)
+        .add_attribute("content_hash", current_hash)
+        .add_attribute("threshold", threshold.to_string()))
+}
+
+pub fn execute_record_signature(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    request_id: u64,
+    decision: SignatureDecision,
+) -> Result<Response, ContractError> {
+    let mut request = SIGNATURE_REQUESTS.load(deps.storage, request_id)?;
+
+    if request.status != SignatureStatus::Pending {
+        return Err(StdError::generic_err("Signature request is no longer pending").into());
+    }
+    if !request.signers.contains(&info.sender) {
+        return Err(StdError::generic_err("Unauthorized: Not a signer of this request").into());
+    }
+    if request.signatures.iter().any(|s| s.signer == info.sender) {
+        return Err(StdError::generic_err("Signer has already recorded a decision").into());
+    }
+
+    request.signatures.push(Signature {
+        signer: info.sender.clone(),
+        decision: decision.clone(),
+        signed_at: env.block.time,
+    });
+
+    let signed = request.signatures.iter()
+        .filter(|s| s.decision == SignatureDecision::Sign)
+        .count() as u32;
+    let rejected = request.signatures.iter()
+        .filter(|s| s.decision == SignatureDecision::Reject)
+        .count() as u32;
+    let remaining = request.signers.len() as u32 - signed - rejected;
+
+    if signed >= request.threshold {
+        request.status = SignatureStatus::Executed;
+        request.executed_at = Some(env.block.time);
+    } else if signed + remaining < request.threshold {
+        // Threshold can no longer be reached
+        request.status = SignatureStatus::Rejected;
+    }
+
+    if request.status != SignatureStatus::Pending {
+        let key = (request.collection.clone(), request.document.clone());
+        remove_pending_request(deps.storage, key, request_id)?;
+    }
+    SIGNATURE_REQUESTS.save(deps.storage, request_id, &request)?;
+
+    let decision = match decision {
+        SignatureDecision::Sign => "sign",
+        SignatureDecision::Reject => "reject",
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "record_signature")
+        .add_attribute("request_id", request_id.to_string())
+        .add_attribute("signer", info.sender)
+        .add_attribute("decision", decision)
+        .add_attribute("status", format!("{:?}", request.status)))
+}
+
+// Called on every write to a document: pending signatures no longer match its content
+pub fn invalidate_signature_requests(
+    storage: &mut dyn Storage,
+    collection: &str,
+    document_id: &str,
+) -> StdResult<()> {
+    let key = (collection.to_string(), document_id.to_string());
+    let pending = match DOCUMENT_SIGNATURE_REQUESTS.may_load(storage, key.clone())? {
+        Some(pending) => pending,
+        None => return Ok(()),
+    };
+
+    for request_id in pending {
+        let mut request = SIGNATURE_REQUESTS.load(storage, request_id)?;
+        if request.status == SignatureStatus::Pending {
+            request.status = SignatureStatus::Invalidated;
+            SIGNATURE_REQUESTS.save(storage, request_id, &request)?;
+        }
+    }
+    DOCUMENT_SIGNATURE_REQUESTS.remove(storage, key);
+
+    Ok(())
+}
+
+fn remove_pending_request(
+    storage: &mut dyn Storage,
+    key: (String, String),
+    request_id: u64,
+) -> StdResult<()> {
+    let mut pending = DOCUMENT_SIGNATURE_REQUESTS.may_load(storage, key.clone())?.unwrap_or_default();
+    pending.retain(|id| *id != request_id);
+    if pending.is_empty() {
+        DOCUMENT_SIGNATURE_REQUESTS.remove(storage, key);
+    } else {
+        DOCUMENT_SIGNATURE_REQUESTS.save(storage, key, &pending)?;
+    }
+    Ok(())
+}
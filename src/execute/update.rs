@@ -1,10 +1,20 @@
 use cosmwasm_std::{
-    Addr, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    DepsMut, Env, MessageInfo, Response, StdError,
 };
 use serde_json;
 
-use crate::state::{Document, DOCUMENTS, ADMIN};
-use crate::query::check_permission;
+use crate::error::ContractError;
+use crate::state::{ChangeOp, WriteKind, DOCUMENTS};
+use crate::query::{check_document_permission, RequestContext};
+use crate::query::fields::check_field_writes;
+use crate::execute::changes::record_change;
+use crate::execute::hooks::dispatch_hooks;
+use crate::execute::events::write_event;
+use crate::execute::pause::check_not_paused;
+use crate::execute::pricing::{charge_write, Payment};
+use crate::execute::quota::record_usage;
+use crate::execute::rate_limit::consume_rate_limit;
+use crate::execute::signing::invalidate_signature_requests;
 
 pub fn execute_update(
     deps: DepsMut,
@@ -13,44 +23,71 @@ pub fn execute_update(
     collection: String,
     document_id: String,
     data: String,
-) -> StdResult<Response> {
+    payment: &mut Payment,
+) -> Result<Response, ContractError> {
+    update_document(deps, env, info, collection, document_id, data, payment, true)
+}
+
+// `rate_limited` is false for bulk updates, which count once per call
+#[allow(clippy::too_many_arguments)]
+pub fn update_document(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    document_id: String,
+    data: String,
+    payment: &mut Payment,
+    rate_limited: bool,
+) -> Result<Response, ContractError> {
+    check_not_paused(deps.storage, &collection, WriteKind::Update)?;
+    
     let key = (collection.clone(), document_id.clone());
     
     // Load existing document
     let mut doc = DOCUMENTS.load(deps.storage, key.clone())?;
     
-    // Check if user owns document OR has update permission for collection
-    let admin = ADMIN.load(deps.storage)?;
-    let owns_document = doc.owner == info.sender;
-    let is_admin = info.sender == admin;
-    let has_update_permission = check_permission(deps.as_ref(), &collection, &info.sender, "update")?;
-    
-    if !owns_document && !is_admin && !has_update_permission {
-        return Err(StdError::generic_err("Unauthorized: Must own document or have update permission"));
-    }
-    
     // Merge JSON data
     let existing: serde_json::Value = serde_json::from_str(&doc.data)
         .map_err(|e| StdError::generic_err(e.to_string()))?;
     let new_data: serde_json::Value = serde_json::from_str(&data)
         .map_err(|e| StdError::generic_err(e.to_string()))?;
+    let merged = merge_json(existing.clone(), new_data);
+    let merged_data = serde_json::to_string(&merged)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
     
-    let merged = merge_json(existing, new_data);
+    // Check if user owns document, is shared on it OR has update permission for collection.
+    // Rules see the document as it will be after the merge.
+    let request = RequestContext { data: Some(&merged_data), time: Some(env.block.time) };
+    if !check_document_permission(deps.as_ref(), &collection, &document_id, &doc, &info.sender, "update", &request)? {
+        return Err(StdError::generic_err("Unauthorized: Must own document or have update permission").into());
+    }
+    if rate_limited {
+        consume_rate_limit(&mut deps, &env, &collection, &info.sender)?;
+    }
     
-    doc.data = serde_json::to_string(&merged)
-        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    let previous = doc.clone();
+    check_field_writes(deps.as_ref(), &collection, &info.sender, &doc.owner, Some(&existing), &merged, env.block.time)?;
+    
+    doc.data = merged_data;
     doc.updated_at = env.block.time;
+    charge_write(deps.storage, payment, &collection, &document_id, &info.sender, doc.data.len(), false)?;
     
+    record_usage(deps.storage, &collection, Some(&previous), Some(&doc))?;
     DOCUMENTS.save(deps.storage, key, &doc)?;
+    invalidate_signature_requests(deps.storage, &collection, &document_id)?;
+    let change = record_change(deps.storage, &env, &collection, &document_id, ChangeOp::Update, Some(&doc))?;
     
-    Ok(Response::new()
+    let response = Response::new()
         .add_attribute("action", "update")
         .add_attribute("collection", collection)
-        .add_attribute("document", document_id))
+        .add_attribute("document", document_id)
+        .add_event(write_event(&change, &info.sender, Some(&doc)));
+    Ok(dispatch_hooks(deps.storage, &change, &info.sender, response)?)
 }
 
 // Helper function to merge JSON objects
-fn merge_json(mut existing: serde_json::Value, new: serde_json::Value) -> serde_json::Value {
+pub fn merge_json(mut existing: serde_json::Value, new: serde_json::Value) -> serde_json::Value {
     if let (serde_json::Value::Object(ref mut existing_map), serde_json::Value::Object(new_map)) = (&mut existing, &new) {
         for (key, value) in new_map {
             existing_map.insert(key.clone(), value.clone());
@@ -0,0 +1,113 @@
+use cosmwasm_std::{
+    Api, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+};
+
+use crate::error::ContractError;
+use crate::state::{ChangeOp, CollectionWorkflow, WriteKind, ADMIN, COLLECTION_WORKFLOWS, DOCUMENTS};
+use crate::execute::changes::record_change;
+use crate::execute::hooks::dispatch_hooks;
+use crate::execute::events::{permission_event, write_event};
+use crate::execute::pause::check_not_paused;
+use crate::execute::signing::invalidate_signature_requests;
+use crate::query::evaluate_permission;
+
+pub fn execute_set_workflow(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    collection: String,
+    workflow: Option<CollectionWorkflow>,
+) -> Result<Response, ContractError> {
+    // Only admin, collection owners manage access but not lifecycles
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() != Some(&info.sender) {
+        return Err(StdError::generic_err("Only admin can set collection workflows").into());
+    }
+    
+    match workflow {
+        Some(workflow) => {
+            validate_workflow(deps.api, &workflow)?;
+            COLLECTION_WORKFLOWS.save(deps.storage, collection.clone(), &workflow)?;
+        }
+        None => COLLECTION_WORKFLOWS.remove(deps.storage, collection.clone()),
+    }
+    
+    Ok(Response::new()
+        .add_event(permission_event("workflow", &collection, &info.sender))
+        .add_attribute("action", "set_workflow")
+        .add_attribute("collection", collection))
+}
+
+pub fn execute_transition(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: String,
+    document_id: String,
+    to_state: String,
+) -> Result<Response, ContractError> {
+    check_not_paused(deps.storage, &collection, WriteKind::Update)?;
+    
+    let workflow = COLLECTION_WORKFLOWS.may_load(deps.storage, collection.clone())?
+        .ok_or_else(|| StdError::generic_err("Collection has no workflow"))?;
+    
+    let key = (collection.clone(), document_id.clone());
+    let mut doc = DOCUMENTS.load(deps.storage, key.clone())?;
+    let from_state = doc.status.clone().unwrap_or_else(|| workflow.initial_state.clone());
+    
+    let transition = workflow.transitions.iter()
+        .find(|t| t.from == from_state && t.to == to_state)
+        .ok_or_else(|| StdError::generic_err(format!("Transition from {} to {} is not allowed", from_state, to_state)))?;
+    
+    let admin = ADMIN.may_load(deps.storage)?;
+    let is_admin = admin.as_ref() == Some(&info.sender);
+    if !is_admin && !evaluate_permission(deps.as_ref(), &transition.permission, &info.sender, admin.as_ref(), Some(&doc.owner), Some(env.block.time))? {
+        return Err(StdError::generic_err("Insufficient permissions for this transition").into());
+    }
+    
+    doc.status = Some(to_state.clone());
+    doc.updated_at = env.block.time;
+    DOCUMENTS.save(deps.storage, key, &doc)?;
+    // Signers approved the document in its previous state
+    invalidate_signature_requests(deps.storage, &collection, &document_id)?;
+    let change = record_change(deps.storage, &env, &collection, &document_id, ChangeOp::Transition, Some(&doc))?;
+    
+    let response = Response::new()
+        .add_attribute("action", "transition")
+        .add_attribute("collection", collection)
+        .add_attribute("document", document_id)
+        .add_attribute("from", from_state)
+        .add_attribute("to", to_state)
+        .add_event(write_event(&change, &info.sender, Some(&doc)));
+    Ok(dispatch_hooks(deps.storage, &change, &info.sender, response)?)
+}
+
+fn validate_workflow(api: &dyn Api, workflow: &CollectionWorkflow) -> StdResult<()> {
+    if workflow.states.is_empty() {
+        return Err(StdError::generic_err("Workflow must define at least one state"));
+    }
+    for (i, state) in workflow.states.iter().enumerate() {
+        if workflow.states[..i].contains(state) {
+            return Err(StdError::generic_err(format!("Duplicate workflow state: {}", state)));
+        }
+    }
+    
+    let known = |state: &String| -> StdResult<()> {
+        if workflow.states.contains(state) {
+            Ok(())
+        } else {
+            Err(StdError::generic_err(format!("Unknown workflow state: {}", state)))
+        }
+    };
+    
+    known(&workflow.initial_state)?;
+    for transition in &workflow.transitions {
+        known(&transition.from)?;
+        known(&transition.to)?;
+    }
+    for overrides in &workflow.state_permissions {
+        known(&overrides.state)?;
+    }
+    
+    workflow.validate_permissions(api)
+}
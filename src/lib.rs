@@ -1,6 +1,9 @@
 pub mod contract;
 mod error;
+pub mod execute;
 pub mod msg;
+pub mod query;
+pub mod rules;
 pub mod state;
 
 pub use crate::error::ContractError;
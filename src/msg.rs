@@ -1,8 +1,10 @@
-use cosmwasm_std::Addr;
+use cosmwasm_std::{to_json_binary, Addr, CosmosMsg, StdResult, Timestamp, Uint128, WasmMsg};
+use cw20::Cw20ReceiveMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use crate::state::Document;
-use crate::state::CollectionPermissions;
+use crate::state::{AclEntry, AdminAction, Change, AdminProposal, CollectionPricing, Hook, HookMode, PauseScope, Quota, RateLimit, TokenDenom, Usage, CollectionPermissions, CollectionWorkflow, DocumentAction, FieldRule, RoleGrant};
+use crate::state::{SignatureDecision, SignatureRequest};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
@@ -29,6 +31,22 @@ pub enum ExecuteMsg {
         document: String,
     },
     // Batch operations
+    // Apply to documents matching a filter, scanning at most limit documents
+    // per call. Continue from the next_start_after in the response data.
+    // Each call counts as one write against the collection rate limit.
+    DeleteWhere {
+        collection: String,
+        filter: String,  // JSON object of field path to exact value
+        limit: Option<u32>,
+        start_after: Option<String>,
+    },
+    UpdateWhere {
+        collection: String,
+        filter: String,
+        merge: String,   // Merged into each matching document like Update
+        limit: Option<u32>,
+        start_after: Option<String>,
+    },
     BatchWrite {
         operations: Vec<WriteOperation>,
     },
@@ -40,14 +58,208 @@ pub enum ExecuteMsg {
     GrantRole {
         user: String,
         role: String,
+        expires_at: Option<Timestamp>,  // Role lapses at this block time
     },
     RevokeRole {
         user: String,
         role: String,
     },
-    TransferAdmin {
+    // Admin transfer: the proposed address must accept before it takes effect
+    ProposeAdmin {
         new_admin: String,
+        expires_at: Option<Timestamp>,  // Proposal can't be accepted after this block time
+    },
+    AcceptAdmin {},
+    CancelAdminProposal {},
+    // Leaves the contract without an admin. Irreversible
+    RenounceAdmin {},
+    // Hands admin rights to an M-of-N set of members. Existing role managers
+    // and collection owners keep acting alone until the set removes them
+    SetAdminSet {
+        members: Vec<String>,
+        threshold: u32,
+    },
+    // Admin set members propose privileged messages; they run once approved
+    ProposeAdminAction {
+        msg: Box<ExecuteMsg>,
+        expires_at: Option<Timestamp>,
+    },
+    ApproveAdminAction {
+        action_id: u64,
+    },
+    CancelAdminAction {
+        action_id: u64,
+    },
+    // Role hierarchy, e.g. moderator inherits editor. Empty removes it
+    SetRoleInherits {
+        role: String,
+        inherits: Vec<String>,
+    },
+    // Delegation: role managers grant/revoke one role, collection owners
+    // manage the permissions, rules, field rules and pricing of one collection
+    SetRoleManager {
+        role: String,
+        manager: String,
+        enabled: bool,
+    },
+    SetCollectionOwner {
+        collection: String,
+        owner: String,
+        enabled: bool,
+    },
+    // Drops expired role grants of a user, callable by anyone
+    PruneExpiredRoles {
+        user: String,
+    },
+    // Multi-party signing
+    RequestSignatures {
+        collection: String,
+        document: String,
+        signers: Vec<String>,
+        threshold: u32,
+        content_hash: Option<String>,  // Fails if the document no longer matches
+    },
+    RecordSignature {
+        request_id: u64,
+        decision: SignatureDecision,
+    },
+    // Document lifecycle
+    SetCollectionWorkflow {
+        collection: String,
+        workflow: Option<CollectionWorkflow>,  // None removes the workflow
+    },
+    Transition {
+        collection: String,
+        document: String,
+        to_state: String,
+    },
+    // Document-level sharing
+    SetDocumentAcl {
+        collection: String,
+        document: String,
+        entries: Vec<AclEntry>,  // Replaces the whole ACL, empty removes it
+    },
+    // Security rules, e.g. "allow update: if request.auth == resource.owner;"
+    SetCollectionRules {
+        collection: String,
+        rules: Option<String>,  // None removes the rules
+    },
+    // Read rules only shape query output, they are not confidentiality
+    SetFieldRules {
+        collection: String,
+        rules: Vec<FieldRule>,  // Replaces all field rules, empty removes them
+    },
+    // Paid writes
+    SetCollectionPricing {
+        collection: String,
+        pricing: Option<CollectionPricing>,  // None makes writes free, cw20 tokens must be accepted
+    },
+    SetAcceptedCw20 {
+        contract: String,
+        enabled: bool,
+    },
+    WithdrawFees {
+        token: TokenDenom,
+        amount: Option<Uint128>,     // Defaults to everything collected
+        recipient: Option<String>,   // Defaults to the sender
+    },
+    // Storage quotas per owner
+    SetCollectionQuota {
+        collection: String,
+        quota: Option<Quota>,  // None removes the quota
+    },
+    SetGlobalQuota {
+        quota: Option<Quota>,
+    },
+    SetCollectionRateLimit {
+        collection: String,
+        limit: Option<RateLimit>,  // None removes the limit
     },
+    // Registering a contract that is already a hook updates its mode. Hooks
+    // only see Set, Update, Delete and Transition writes, collection drops,
+    // renames and copies and imports don't dispatch them.
+    AddHook {
+        collection: String,
+        contract: String,
+        mode: HookMode,
+    },
+    RemoveHook {
+        collection: String,
+        contract: String,
+    },
+    // Emergency stop for document writes
+    Pause {
+        scope: PauseScope,
+        reason: Option<String>,
+    },
+    Unpause {
+        scope: PauseScope,
+    },
+    // Snapshot restore. Records must be sent in export order, FinishImport
+    // checks the totals against the export
+    Import {
+        version: u32,
+        records: Vec<ExportRecord>,
+    },
+    FinishImport {
+        records: u64,
+        checksum: String,
+    },
+    // Collection maintenance, run in chunks of at most limit documents.
+    // Repeat the message until the response reports done=true.
+    DropCollection {
+        collection: String,
+        limit: Option<u32>,
+    },
+    RenameCollection {
+        from: String,
+        to: String,
+        limit: Option<u32>,
+    },
+    CopyCollection {
+        from: String,
+        to: String,
+        limit: Option<u32>,
+    },
+    // cw20 Send hook, msg is a Set, Update, UpdateWhere or BatchWrite paid with the sent tokens
+    Receive(Cw20ReceiveMsg),
+}
+
+impl ExecuteMsg {
+    // Messages that can be proposed as admin actions
+    pub fn is_privileged(&self) -> bool {
+        matches!(
+            self,
+            ExecuteMsg::SetCollectionPermissions { .. }
+                | ExecuteMsg::GrantRole { .. }
+                | ExecuteMsg::RevokeRole { .. }
+                | ExecuteMsg::ProposeAdmin { .. }
+                | ExecuteMsg::CancelAdminProposal {}
+                | ExecuteMsg::RenounceAdmin {}
+                | ExecuteMsg::SetAdminSet { .. }
+                | ExecuteMsg::SetRoleInherits { .. }
+                | ExecuteMsg::SetRoleManager { .. }
+                | ExecuteMsg::SetCollectionOwner { .. }
+                | ExecuteMsg::SetCollectionWorkflow { .. }
+                | ExecuteMsg::SetCollectionRules { .. }
+                | ExecuteMsg::SetFieldRules { .. }
+                | ExecuteMsg::SetCollectionPricing { .. }
+                | ExecuteMsg::SetAcceptedCw20 { .. }
+                | ExecuteMsg::WithdrawFees { .. }
+                | ExecuteMsg::SetCollectionQuota { .. }
+                | ExecuteMsg::SetGlobalQuota { .. }
+                | ExecuteMsg::SetCollectionRateLimit { .. }
+                | ExecuteMsg::AddHook { .. }
+                | ExecuteMsg::RemoveHook { .. }
+                | ExecuteMsg::Pause { .. }
+                | ExecuteMsg::Unpause { .. }
+                | ExecuteMsg::DropCollection { .. }
+                | ExecuteMsg::RenameCollection { .. }
+                | ExecuteMsg::CopyCollection { .. }
+                | ExecuteMsg::Import { .. }
+                | ExecuteMsg::FinishImport { .. }
+        )
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -70,15 +282,19 @@ pub enum WriteType {
 #[serde(rename_all = "camelCase")]
 pub enum QueryMsg {
     // Get single document
+    // `viewer` is used to strip fields the viewer cannot read. Anyone can pass
+    // any viewer and chain state is public, so this shapes output only
     Get {
         collection: String,
         document: String,
+        viewer: Option<String>,
     },
     // List documents in collection
     Collection {
         collection: String,
         limit: Option<u32>,
         start_after: Option<String>,
+        viewer: Option<String>,
     },
     // List documents by owner
     UserDocuments {
@@ -86,6 +302,7 @@ pub enum QueryMsg {
         collection: Option<String>,
         limit: Option<u32>,
         start_after: Option<String>,
+        viewer: Option<String>,
     },
     // Permission queries
     GetCollectionPermissions {
@@ -102,7 +319,87 @@ pub enum QueryMsg {
     // New query types
     GetDocumentStats { collection: String },
     ListCollections { limit: Option<u32>, start_after: Option<String> },
-    SearchDocuments { collection: String, query: String, limit: Option<u32>, start_after: Option<String> },
+    SearchDocuments { collection: String, query: String, limit: Option<u32>, start_after: Option<String>, viewer: Option<String> },
+    // Signature queries
+    GetSignatureRequest { request_id: u64 },
+    ListSignatureRequests {
+        collection: String,
+        document: String,
+        limit: Option<u32>,
+        start_after: Option<u64>,
+    },
+    // Workflow queries
+    GetCollectionWorkflow { collection: String },
+    CheckDocumentPermission {
+        collection: String,
+        document: String,
+        user: String,
+        action: String, // "update", "delete", "read", "share"
+    },
+    // Document ACL queries
+    GetDocumentAcl {
+        collection: String,
+        document: String,
+    },
+    ListSharedDocuments {
+        user: String,
+        limit: Option<u32>,
+        start_after: Option<(String, String)>,  // (collection, document)
+    },
+    GetCollectionRules { collection: String },
+    GetFieldRules { collection: String },
+    GetRoleGrant { user: String, role: String },
+    ListUserRoleGrants { user: String },
+    GetRoleInherits { role: String },
+    GetEffectiveRoles { user: String },
+    ListRoleMembers {
+        role: String,
+        limit: Option<u32>,
+        start_after: Option<String>,
+    },
+    ListRoles {
+        limit: Option<u32>,
+        start_after: Option<String>,
+    },
+    ListRoleManagers { role: String },
+    ListCollectionOwners { collection: String },
+    GetAdmin {},
+    GetAdminSet {},
+    GetCollectionPricing { collection: String },
+    GetDocumentDeposit { collection: String, document: String },
+    ListCollectedFees {},
+    ListAcceptedCw20 {},
+    GetCollectionQuota { collection: String },
+    GetGlobalQuota {},
+    GetUserUsage {
+        user: String,
+        collection: Option<String>,  // Also include usage and limits for this collection
+    },
+    GetCollectionRateLimit { collection: String },
+    GetRateLimitStatus { collection: String, user: String },
+    ListHooks { collection: String },
+    GetPauseStatus {},
+    GetCollectionOp { collection: String },
+    // Returns documents unredacted, field read rules do not apply
+    Export {
+        cursor: Option<ExportCursor>,
+        checksum: Option<String>,  // checksum of the previous page
+        records_total: Option<u64>,  // records_total of the previous page
+        limit: Option<u32>,
+    },
+    GetImportProgress {},
+    // Change feed entries after sequence, oldest first
+    ChangesSince {
+        sequence: u64,
+        collection: Option<String>,
+        limit: Option<u32>,
+    },
+    GetAdminAction { action_id: u64 },
+    ListAdminActions {
+        pending_only: Option<bool>,
+        limit: Option<u32>,
+        start_after: Option<u64>,
+    },
 }
 
 // Response types
@@ -157,4 +454,172 @@ pub struct SearchResult {
 pub struct SearchResponse {
     pub results: Vec<SearchResult>,
     pub next_start_after: Option<String>,
-}
\ No newline at end of file
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureRequestListResponse {
+    pub requests: Vec<SignatureRequest>,
+    pub next_start_after: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedDocument {
+    pub collection: String,
+    pub document: String,
+    pub actions: Vec<DocumentAction>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedDocumentsResponse {
+    pub documents: Vec<SharedDocument>,
+    pub next_start_after: Option<(String, String)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RoleGrantInfo {
+    pub role: String,
+    pub grant: RoleGrant,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RoleMember {
+    pub user: Addr,
+    pub expires_at: Option<Timestamp>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RoleMembersResponse {
+    pub members: Vec<RoleMember>,
+    pub next_start_after: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RoleInfo {
+    pub role: String,
+    pub member_count: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RoleListResponse {
+    pub roles: Vec<RoleInfo>,
+    pub next_start_after: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminResponse {
+    pub admin: Option<Addr>,
+    pub pending: Option<AdminProposal>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminActionListResponse {
+    pub actions: Vec<AdminAction>,
+    pub next_start_after: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UserUsageResponse {
+    pub usage: Usage,                      // Across all collections
+    pub collection_usage: Option<Usage>,   // In the requested collection
+    pub remaining_documents: Option<u64>,  // None when unlimited
+    pub remaining_bytes: Option<u64>,
+    pub max_document_size: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitStatusResponse {
+    pub limit: Option<RateLimit>,
+    pub exempt: bool,
+    pub remaining_writes: Option<u32>,  // None when not limited
+    pub retry_after: Option<u64>,       // Set when no writes remain, in the window's unit
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkWriteResponse {
+    pub scanned: u32,
+    pub matched: u32,
+    pub processed: u32,                    // Matched and permitted, including field write rules
+    pub next_start_after: Option<String>,  // None once the collection is exhausted
+}
+
+// Snapshot format version, bumped on any change to ExportRecord
+pub const EXPORT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportRecord {
+    CollectionPermissions { collection: String, permissions: CollectionPermissions },
+    UserRoles { user: Addr, grants: Vec<RoleGrantInfo> },
+    Document { collection: String, id: String, document: Document },
+}
+
+// Export walks permissions, then roles, then documents
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportCursor {
+    Permissions(String),
+    Roles(Addr),
+    Documents(String, String),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportResponse {
+    pub version: u32,
+    pub records: Vec<ExportRecord>,
+    pub checksum: String,  // Running checksum including this page
+    pub records_total: u64,
+    pub next_cursor: Option<ExportCursor>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangesResponse {
+    pub changes: Vec<Change>,
+    pub next_sequence: u64,    // Pass as sequence to continue
+    pub latest_sequence: u64,  // Caught up once next_sequence reaches this
+}
+
+// Sent to hook contracts after a write. Hooks must accept
+// {"docustoreHook": {...}} as an execute message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DocustoreHookMsg {
+    pub change: Change,
+    pub actor: Addr,
+}
+
+impl DocustoreHookMsg {
+    pub fn into_cosmos_msg(self, contract: &Addr) -> StdResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: contract.to_string(),
+            msg: to_json_binary(&HookExecuteMsg::DocustoreHook(self))?,
+            funds: vec![],
+        }.into())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+enum HookExecuteMsg {
+    DocustoreHook(DocustoreHookMsg),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HooksResponse {
+    pub hooks: Vec<Hook>,
+}
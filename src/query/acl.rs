@@ -0,0 +1,74 @@
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, Env, StdResult, Order,
+};
+use cw_storage_plus::Bound;
+
+use crate::msg::{SharedDocument, SharedDocumentsResponse};
+use crate::state::{Principal, DOCUMENT_ACLS, SHARED_DOCUMENTS};
+use super::effective_roles;
+
+pub fn query_document_acl(
+    deps: Deps,
+    collection: String,
+    document: String,
+) -> StdResult<Binary> {
+    let entries = DOCUMENT_ACLS.may_load(deps.storage, (collection, document))?
+        .unwrap_or_default();
+    to_json_binary(&entries)
+}
+
+// Documents shared with a user directly or through one of their roles
+pub fn query_shared_documents(
+    deps: Deps,
+    env: Env,
+    user: String,
+    limit: Option<u32>,
+    start_after: Option<(String, String)>,
+) -> StdResult<Binary> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let limit = limit.unwrap_or(30) as usize;
+    
+    let mut principals = vec![Principal::Address(user_addr.to_string())];
+    let roles = effective_roles(deps, &user_addr, Some(env.block.time))?;
+    principals.extend(roles.into_iter().map(Principal::Role));
+    
+    // Take the first `limit` entries of every principal, then merge them
+    let mut documents: Vec<SharedDocument> = Vec::new();
+    for principal in principals {
+        let start = start_after.clone().map(Bound::exclusive);
+        let entries = SHARED_DOCUMENTS
+            .sub_prefix(principal.index_key())
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit);
+        
+        for item in entries {
+            let ((collection, document), actions) = item?;
+            match documents.iter_mut().find(|d| d.collection == collection && d.document == document) {
+                Some(existing) => {
+                    for action in actions {
+                        if !existing.actions.contains(&action) {
+                            existing.actions.push(action);
+                        }
+                    }
+                }
+                None => documents.push(SharedDocument { collection, document, actions }),
+            }
+        }
+    }
+    
+    documents.sort_by(|a, b| (&a.collection, &a.document).cmp(&(&b.collection, &b.document)));
+    documents.truncate(limit);
+    
+    let next_start_after = if documents.len() == limit {
+        documents.last().map(|d| (d.collection.clone(), d.document.clone()))
+    } else {
+        None
+    };
+    
+    let response = SharedDocumentsResponse {
+        documents,
+        next_start_after,
+    };
+    
+    to_json_binary(&response)
+}
@@ -0,0 +1,60 @@
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, StdResult, Order,
+};
+use cw_storage_plus::Bound;
+
+use crate::msg::{AdminActionListResponse, AdminResponse};
+use crate::state::{AdminAction, AdminActionStatus, ADMIN, ADMIN_ACTIONS, ADMIN_SET, PENDING_ADMIN};
+
+pub fn query_admin(deps: Deps) -> StdResult<Binary> {
+    to_json_binary(&AdminResponse {
+        admin: ADMIN.may_load(deps.storage)?,
+        pending: PENDING_ADMIN.may_load(deps.storage)?,
+    })
+}
+
+pub fn query_admin_set(deps: Deps) -> StdResult<Binary> {
+    to_json_binary(&ADMIN_SET.may_load(deps.storage)?)
+}
+
+pub fn query_admin_action(
+    deps: Deps,
+    action_id: u64,
+) -> StdResult<Binary> {
+    let action = ADMIN_ACTIONS.load(deps.storage, action_id)?;
+    to_json_binary(&action)
+}
+
+pub fn query_list_admin_actions(
+    deps: Deps,
+    pending_only: Option<bool>,
+    limit: Option<u32>,
+    start_after: Option<u64>,
+) -> StdResult<Binary> {
+    let pending_only = pending_only.unwrap_or(false);
+    let limit = limit.unwrap_or(30) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let actions: Vec<AdminAction> = ADMIN_ACTIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter_map(|item| {
+            let (_, action) = item.ok()?;
+            if pending_only && action.status != AdminActionStatus::Pending {
+                return None;
+            }
+            Some(action)
+        })
+        .take(limit)
+        .collect();
+
+    let next_start_after = if actions.len() == limit {
+        actions.last().map(|a| a.id)
+    } else {
+        None
+    };
+
+    to_json_binary(&AdminActionListResponse {
+        actions,
+        next_start_after,
+    })
+}
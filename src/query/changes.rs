@@ -0,0 +1,42 @@
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, Order, StdResult,
+};
+use cw_storage_plus::Bound;
+
+use crate::msg::ChangesResponse;
+use crate::state::{Change, CHANGES, CHANGE_SEQUENCE};
+
+pub fn query_changes_since(
+    deps: Deps,
+    sequence: u64,
+    collection: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(30).min(200) as usize;
+    let start = Some(Bound::exclusive(sequence));
+
+    let changes: Vec<Change> = match collection {
+        Some(collection) => CHANGES
+            .idx
+            .collection
+            .prefix(collection)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, change)| change))
+            .collect::<StdResult<Vec<_>>>()?,
+        None => CHANGES
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, change)| change))
+            .collect::<StdResult<Vec<_>>>()?,
+    };
+
+    // Clients resume from the last sequence they received
+    let next_sequence = changes.last().map(|c| c.sequence).unwrap_or(sequence);
+
+    to_json_binary(&ChangesResponse {
+        changes,
+        next_sequence,
+        latest_sequence: CHANGE_SEQUENCE.may_load(deps.storage)?.unwrap_or_default(),
+    })
+}
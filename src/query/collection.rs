@@ -1,30 +1,42 @@
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, Deps, StdResult, Order,
+    to_json_binary, Binary, Deps, Env, StdResult, Order,
 };
 use cw_storage_plus::Bound;
 
 use crate::msg::CollectionResponse;
-use crate::state::{Document, DOCUMENTS};
+use crate::state::{Document, COLLECTION_FIELD_RULES, COLLECTION_OPS, DOCUMENTS};
+use super::fields::{redact_document, redact_with_rules};
+use super::state_allows_read;
 
 pub fn query_collection(
     deps: Deps,
+    env: Env,
     collection: String,
     limit: Option<u32>,
     start_after: Option<String>,
+    viewer: Option<String>,
 ) -> StdResult<Binary> {
     let limit = limit.unwrap_or(30) as usize;
+    let viewer = viewer.map(|v| deps.api.addr_validate(&v)).transpose()?;
+    let field_rules = COLLECTION_FIELD_RULES.may_load(deps.storage, collection.clone())?
+        .unwrap_or_default();
     
     let start = start_after.as_ref().map(|s| Bound::exclusive((collection.clone(), s.clone())));
     let end = Bound::exclusive((format!("{}~", collection), String::new()));
     
-    let documents: Vec<(String, Document)> = DOCUMENTS
-        .range(deps.storage, start, Some(end), Order::Ascending)
-        .take(limit)
-        .map(|item| {
-            let ((_, doc_id), doc) = item?;
-            Ok((doc_id, doc))
-        })
-        .collect::<StdResult<Vec<_>>>()?;
+    // Documents hidden by their workflow state are skipped
+    let mut documents: Vec<(String, Document)> = Vec::new();
+    for item in DOCUMENTS.range(deps.storage, start, Some(end), Order::Ascending) {
+        let ((_, doc_id), mut doc) = item?;
+        if !state_allows_read(deps, &collection, &doc, viewer.as_ref(), env.block.time)? {
+            continue;
+        }
+        redact_with_rules(deps, &field_rules, &mut doc, viewer.as_ref(), env.block.time)?;
+        documents.push((doc_id, doc));
+        if documents.len() >= limit {
+            break;
+        }
+    }
     
     let next_start_after = if documents.len() == limit {
         documents.last().map(|(id, _)| id.clone())
@@ -42,12 +54,15 @@ pub fn query_collection(
 
 pub fn query_user_documents(
     deps: Deps,
+    env: Env,
     owner: String,
     collection: Option<String>,
     limit: Option<u32>,
     start_after: Option<String>,
+    viewer: Option<String>,
 ) -> StdResult<Binary> {
     let owner_addr = deps.api.addr_validate(&owner)?;
+    let viewer = viewer.map(|v| deps.api.addr_validate(&v)).transpose()?;
     let limit = limit.unwrap_or(30) as usize;
     
     let start = if let (Some(coll), Some(s)) = (collection.clone(), start_after.clone()) {
@@ -72,10 +87,20 @@ pub fn query_user_documents(
                 }
             }
             
-            Some((doc_id, doc))
+            // Skip documents hidden by their workflow state
+            match state_allows_read(deps, &coll, &doc, viewer.as_ref(), env.block.time) {
+                Ok(true) => Some(Ok((coll, doc_id, doc))),
+                Ok(false) => None,
+                Err(e) => Some(Err(e)),
+            }
         })
         .take(limit)
-        .collect();
+        .map(|item| {
+            let (coll, doc_id, mut doc) = item?;
+            redact_document(deps, &coll, &mut doc, viewer.as_ref(), env.block.time)?;
+            Ok((doc_id, doc))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
     
     let next_start_after = if documents.len() == limit {
         documents.last().map(|(id, _)| id.clone())
@@ -89,4 +114,13 @@ pub fn query_user_documents(
     };
     
     to_json_binary(&response)
-} 
\ No newline at end of file
+} 
+
+// Progress of a running drop, rename or copy, None when nothing is running
+pub fn query_collection_op(
+    deps: Deps,
+    collection: String,
+) -> StdResult<Binary> {
+    let op = COLLECTION_OPS.may_load(deps.storage, collection)?;
+    to_json_binary(&op)
+}
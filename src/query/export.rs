@@ -0,0 +1,122 @@
+use cosmwasm_std::{
+    to_json_binary, to_json_vec, Binary, Deps, Env, Order, StdResult, Timestamp,
+};
+use cw_storage_plus::Bound;
+use sha2::{Digest, Sha256};
+
+use crate::msg::{ExportCursor, ExportRecord, ExportResponse, RoleGrantInfo, EXPORT_VERSION};
+use crate::state::{RoleGrant, COLLECTION_PERMISSIONS, DOCUMENTS, IMPORT_PROGRESS, ROLE_GRANTS, USER_ROLES};
+
+const DEFAULT_EXPORT_LIMIT: u32 = 50;
+const MAX_EXPORT_LIMIT: u32 = 200;
+
+// Checksum chained over every record in export order, so both sides can
+// compute it one page at a time
+pub fn chain_checksum(previous: &str, record: &ExportRecord) -> StdResult<String> {
+    let mut hasher = Sha256::new();
+    hasher.update(previous.as_bytes());
+    hasher.update(to_json_vec(record)?);
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+pub fn query_export(
+    deps: Deps,
+    env: Env,
+    cursor: Option<ExportCursor>,
+    checksum: Option<String>,
+    records_total: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_EXPORT_LIMIT).clamp(1, MAX_EXPORT_LIMIT) as usize;
+    let mut records: Vec<ExportRecord> = Vec::new();
+    let mut next_cursor = None;
+
+    // Each section starts from the cursor if it points into it, from the
+    // beginning if the cursor is in an earlier section, and is skipped if later
+    let (permissions_start, roles_start, documents_start) = match cursor {
+        None => (Some(None), Some(None), Some(None)),
+        Some(ExportCursor::Permissions(collection)) => (Some(Some(collection)), Some(None), Some(None)),
+        Some(ExportCursor::Roles(user)) => (None, Some(Some(user)), Some(None)),
+        Some(ExportCursor::Documents(collection, id)) => (None, None, Some(Some((collection, id)))),
+    };
+
+    if let Some(start) = permissions_start {
+        let items = COLLECTION_PERMISSIONS
+            .range(deps.storage, start.map(Bound::exclusive), None, Order::Ascending)
+            .take(limit + 1 - records.len());
+        for item in items {
+            let (collection, permissions) = item?;
+            if records.len() == limit {
+                next_cursor = last_cursor(&records);
+                break;
+            }
+            records.push(ExportRecord::CollectionPermissions { collection, permissions });
+        }
+    }
+
+    if let (Some(start), None) = (roles_start, &next_cursor) {
+        let items = USER_ROLES
+            .range(deps.storage, start.map(Bound::exclusive), None, Order::Ascending)
+            .take(limit + 1 - records.len());
+        for item in items {
+            let (user, roles) = item?;
+            if records.len() == limit {
+                next_cursor = last_cursor(&records);
+                break;
+            }
+            // Roles granted before grant details existed have no ROLE_GRANTS
+            // entry and never expire
+            let grants = roles.into_iter()
+                .map(|role| {
+                    let grant = ROLE_GRANTS.may_load(deps.storage, (user.clone(), role.clone()))?
+                        .unwrap_or_else(|| RoleGrant {
+                            granter: env.contract.address.clone(),
+                            granted_at: Timestamp::from_seconds(0),
+                            expires_at: None,
+                        });
+                    Ok(RoleGrantInfo { role, grant })
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+            records.push(ExportRecord::UserRoles { user, grants });
+        }
+    }
+
+    if let (Some(start), None) = (documents_start, &next_cursor) {
+        let items = DOCUMENTS
+            .range(deps.storage, start.map(Bound::exclusive), None, Order::Ascending)
+            .take(limit + 1 - records.len());
+        for item in items {
+            let ((collection, id), document) = item?;
+            if records.len() == limit {
+                next_cursor = last_cursor(&records);
+                break;
+            }
+            records.push(ExportRecord::Document { collection, id, document });
+        }
+    }
+
+    let mut checksum = checksum.unwrap_or_default();
+    for record in &records {
+        checksum = chain_checksum(&checksum, record)?;
+    }
+
+    to_json_binary(&ExportResponse {
+        version: EXPORT_VERSION,
+        records_total: records_total.unwrap_or_default() + records.len() as u64,
+        records,
+        checksum,
+        next_cursor,
+    })
+}
+
+fn last_cursor(records: &[ExportRecord]) -> Option<ExportCursor> {
+    records.last().map(|record| match record {
+        ExportRecord::CollectionPermissions { collection, .. } => ExportCursor::Permissions(collection.clone()),
+        ExportRecord::UserRoles { user, .. } => ExportCursor::Roles(user.clone()),
+        ExportRecord::Document { collection, id, .. } => ExportCursor::Documents(collection.clone(), id.clone()),
+    })
+}
+
+pub fn query_import_progress(deps: Deps) -> StdResult<Binary> {
+    to_json_binary(&IMPORT_PROGRESS.may_load(deps.storage)?.unwrap_or_default())
+}
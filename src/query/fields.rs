@@ -0,0 +1,141 @@
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Deps, StdError, StdResult, Timestamp,
+};
+
+use crate::state::{Document, FieldRule, PermissionLevel, ADMIN, COLLECTION_FIELD_RULES};
+use super::evaluate_permission;
+
+pub fn query_field_rules(
+    deps: Deps,
+    collection: String,
+) -> StdResult<Binary> {
+    let rules = COLLECTION_FIELD_RULES.may_load(deps.storage, collection)?
+        .unwrap_or_default();
+    to_json_binary(&rules)
+}
+
+// Fails with the first protected path whose value changes without write permission
+pub fn check_field_writes(
+    deps: Deps,
+    collection: &str,
+    user: &Addr,
+    owner: &Addr,
+    old: Option<&serde_json::Value>,
+    new: &serde_json::Value,
+    time: Timestamp,
+) -> StdResult<()> {
+    match unwritable_field(deps, collection, user, owner, old, new, time)? {
+        Some(path) => Err(StdError::generic_err(format!("Unauthorized: Cannot write field {}", path))),
+        None => Ok(()),
+    }
+}
+
+// The first protected path whose value changes without write permission
+pub fn unwritable_field(
+    deps: Deps,
+    collection: &str,
+    user: &Addr,
+    owner: &Addr,
+    old: Option<&serde_json::Value>,
+    new: &serde_json::Value,
+    time: Timestamp,
+) -> StdResult<Option<String>> {
+    let rules = COLLECTION_FIELD_RULES.may_load(deps.storage, collection.to_string())?
+        .unwrap_or_default();
+    if rules.is_empty() {
+        return Ok(None);
+    }
+    
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.as_ref() == Some(user) {
+        return Ok(None);
+    }
+    
+    for rule in rules {
+        let level = match &rule.write {
+            Some(level) => level,
+            None => continue,
+        };
+        let before = old.and_then(|old| get_path(old, &rule.path));
+        let after = get_path(new, &rule.path);
+        if before == after {
+            continue;
+        }
+        if !evaluate_permission(deps, level, user, admin.as_ref(), Some(owner), Some(time))? {
+            return Ok(Some(rule.path));
+        }
+    }
+    
+    Ok(None)
+}
+
+// Removes the fields the viewer is not allowed to read. Queries are not
+// authenticated, so an absent viewer only sees fields readable by Anyone.
+// The viewer is taken on trust and the raw data stays in public state.
+pub fn redact_document(
+    deps: Deps,
+    collection: &str,
+    doc: &mut Document,
+    viewer: Option<&Addr>,
+    time: Timestamp,
+) -> StdResult<()> {
+    let rules = COLLECTION_FIELD_RULES.may_load(deps.storage, collection.to_string())?
+        .unwrap_or_default();
+    redact_with_rules(deps, &rules, doc, viewer, time)
+}
+
+pub fn redact_with_rules(
+    deps: Deps,
+    rules: &[FieldRule],
+    doc: &mut Document,
+    viewer: Option<&Addr>,
+    time: Timestamp,
+) -> StdResult<()> {
+    if rules.iter().all(|rule| rule.read.is_none()) {
+        return Ok(());
+    }
+    
+    let admin = ADMIN.may_load(deps.storage)?;
+    if admin.is_some() && viewer == admin.as_ref() {
+        return Ok(());
+    }
+    
+    let mut value: serde_json::Value = serde_json::from_str(&doc.data)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    
+    let mut redacted = false;
+    for rule in rules {
+        let level = match &rule.read {
+            Some(level) => level,
+            None => continue,
+        };
+        let readable = match viewer {
+            Some(viewer) => evaluate_permission(deps, level, viewer, admin.as_ref(), Some(&doc.owner), Some(time))?,
+            None => matches!(level, PermissionLevel::Anyone),
+        };
+        if !readable {
+            redacted |= remove_path(&mut value, &rule.path);
+        }
+    }
+    
+    if redacted {
+        doc.data = serde_json::to_string(&value)
+            .map_err(|e| StdError::generic_err(e.to_string()))?;
+    }
+    Ok(())
+}
+
+pub fn get_path<'a>(value: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    path.split('.').try_fold(value, |current, segment| current.get(segment))
+}
+
+fn remove_path(value: &mut serde_json::Value, path: &str) -> bool {
+    let (parent, field) = match path.rsplit_once('.') {
+        Some((parent, field)) => (parent.split('.').try_fold(value, |current, segment| current.get_mut(segment)), field),
+        None => (Some(value), path),
+    };
+    match parent.and_then(|parent| parent.as_object_mut()) {
+        Some(map) => map.remove(field).is_some(),
+        None => false,
+    }
+}
@@ -1,17 +1,31 @@
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, StdResult,
+    to_json_binary, Binary, Deps, Env, StdResult,
 };
 
 use crate::msg::DocumentResponse;
 use crate::state::DOCUMENTS;
+use super::fields::redact_document;
+use super::state_allows_read;
 
 pub fn query_get(
     deps: Deps,
+    env: Env,
     collection: String,
     document_id: String,
+    viewer: Option<String>,
 ) -> StdResult<Binary> {
-    let key = (collection, document_id);
-    let doc = DOCUMENTS.may_load(deps.storage, key)?;
+    let viewer = viewer.map(|v| deps.api.addr_validate(&v)).transpose()?;
+    let key = (collection.clone(), document_id);
+    let mut doc = DOCUMENTS.may_load(deps.storage, key)?;
+    // Documents hidden by their workflow state read as missing
+    if let Some(existing) = &doc {
+        if !state_allows_read(deps, &collection, existing, viewer.as_ref(), env.block.time)? {
+            doc = None;
+        }
+    }
+    if let Some(doc) = doc.as_mut() {
+        redact_document(deps, &collection, doc, viewer.as_ref(), env.block.time)?;
+    }
     
     let response = DocumentResponse {
         exists: doc.is_some(),
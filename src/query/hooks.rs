@@ -0,0 +1,14 @@
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, StdResult,
+};
+
+use crate::msg::HooksResponse;
+use crate::state::COLLECTION_HOOKS;
+
+pub fn query_hooks(
+    deps: Deps,
+    collection: String,
+) -> StdResult<Binary> {
+    let hooks = COLLECTION_HOOKS.may_load(deps.storage, collection)?.unwrap_or_default();
+    to_json_binary(&HooksResponse { hooks })
+}
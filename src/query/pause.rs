@@ -0,0 +1,13 @@
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, Order, StdResult,
+};
+
+use crate::state::{PauseInfo, PAUSES};
+
+pub fn query_pause_status(deps: Deps) -> StdResult<Binary> {
+    let pauses: Vec<PauseInfo> = PAUSES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, pause)| pause))
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&pauses)
+}
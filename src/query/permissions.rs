@@ -1,8 +1,14 @@
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, Deps, StdResult,
+    to_json_binary, Addr, Binary, Deps, Env, Order, StdResult,
 };
+use cw_storage_plus::Bound;
 
-use crate::state::{COLLECTION_PERMISSIONS, USER_ROLES};
+use crate::msg::{RoleGrantInfo, RoleInfo, RoleListResponse, RoleMember, RoleMembersResponse};
+use crate::state::{
+    COLLECTION_OWNERS, COLLECTION_PERMISSIONS, DOCUMENTS, ROLE_GRANTS, ROLE_INHERITS, ROLE_MANAGERS, ROLE_MEMBERS,
+    ROLE_MEMBER_COUNTS,
+};
+use super::RequestContext;
 
 pub fn query_collection_permissions(
     deps: Deps,
@@ -13,23 +19,193 @@ pub fn query_collection_permissions(
     to_json_binary(&permissions)
 }
 
+// Only roles that have not expired yet
 pub fn query_user_roles(
     deps: Deps,
+    env: Env,
     user: String,
 ) -> StdResult<Binary> {
     let user_addr = deps.api.addr_validate(&user)?;
-    let roles = USER_ROLES.may_load(deps.storage, user_addr)?
-        .unwrap_or_default();
+    let roles = super::active_roles(deps, &user_addr, Some(env.block.time))?;
     to_json_binary(&roles)
 }
 
+pub fn query_role_grant(
+    deps: Deps,
+    user: String,
+    role: String,
+) -> StdResult<Binary> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let grant = ROLE_GRANTS.may_load(deps.storage, (user_addr, role))?;
+    to_json_binary(&grant)
+}
+
+// All grants of a user, including expired ones not yet pruned
+pub fn query_user_role_grants(
+    deps: Deps,
+    user: String,
+) -> StdResult<Binary> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let grants: Vec<RoleGrantInfo> = ROLE_GRANTS
+        .prefix(user_addr)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (role, grant) = item?;
+            Ok(RoleGrantInfo { role, grant })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&grants)
+}
+
 pub fn query_check_permission(
     deps: Deps,
+    env: Env,
     collection: String,
     user: String,
     action: String,
 ) -> StdResult<Binary> {
     let user_addr = deps.api.addr_validate(&user)?;
-    let has_permission = super::check_permission(deps, &collection, &user_addr, &action)?;
+    let request = RequestContext { data: None, time: Some(env.block.time) };
+    let has_permission = super::check_permission_with(deps, &collection, &user_addr, &action, None, &request)?;
     to_json_binary(&has_permission)
-} 
\ No newline at end of file
+}
+
+pub fn query_check_document_permission(
+    deps: Deps,
+    env: Env,
+    collection: String,
+    document: String,
+    user: String,
+    action: String,
+) -> StdResult<Binary> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let doc = DOCUMENTS.load(deps.storage, (collection.clone(), document.clone()))?;
+    
+    let request = RequestContext { data: None, time: Some(env.block.time) };
+    let has_permission = super::check_document_permission(deps, &collection, &document, &doc, &user_addr, &action, &request)?;
+    
+    to_json_binary(&has_permission)
+}
+
+pub fn query_role_inherits(
+    deps: Deps,
+    role: String,
+) -> StdResult<Binary> {
+    let inherits = ROLE_INHERITS.may_load(deps.storage, role)?
+        .unwrap_or_default();
+    to_json_binary(&inherits)
+}
+
+// Granted roles that have not expired plus everything they inherit
+pub fn query_effective_roles(
+    deps: Deps,
+    env: Env,
+    user: String,
+) -> StdResult<Binary> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let roles = super::effective_roles(deps, &user_addr, Some(env.block.time))?;
+    to_json_binary(&roles)
+}
+
+// Members whose grant has not expired, paginated by address
+pub fn query_role_members(
+    deps: Deps,
+    env: Env,
+    role: String,
+    limit: Option<u32>,
+    start_after: Option<String>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(30) as usize;
+    let start = start_after
+        .map(|s| deps.api.addr_validate(&s))
+        .transpose()?
+        .map(Bound::exclusive);
+    
+    let members: Vec<RoleMember> = ROLE_MEMBERS
+        .prefix(role.clone())
+        .keys(deps.storage, start, None, Order::Ascending)
+        .filter_map(|item| {
+            let user = match item {
+                Ok(user) => user,
+                Err(e) => return Some(Err(e)),
+            };
+            let grant = match ROLE_GRANTS.may_load(deps.storage, (user.clone(), role.clone())) {
+                Ok(grant) => grant,
+                Err(e) => return Some(Err(e)),
+            };
+            let expires_at = grant.and_then(|grant| grant.expires_at);
+            if expires_at.is_some_and(|expires_at| expires_at <= env.block.time) {
+                return None;
+            }
+            Some(Ok(RoleMember { user, expires_at }))
+        })
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    
+    let next_start_after = if members.len() == limit {
+        members.last().map(|m| m.user.to_string())
+    } else {
+        None
+    };
+    
+    let response = RoleMembersResponse {
+        members,
+        next_start_after,
+    };
+    
+    to_json_binary(&response)
+}
+
+// Member counts include expired grants that have not been pruned yet
+pub fn query_list_roles(
+    deps: Deps,
+    limit: Option<u32>,
+    start_after: Option<String>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(30) as usize;
+    let start = start_after.map(Bound::exclusive);
+    
+    let roles: Vec<RoleInfo> = ROLE_MEMBER_COUNTS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (role, member_count) = item?;
+            Ok(RoleInfo { role, member_count })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    
+    let next_start_after = if roles.len() == limit {
+        roles.last().map(|r| r.role.clone())
+    } else {
+        None
+    };
+    
+    let response = RoleListResponse {
+        roles,
+        next_start_after,
+    };
+    
+    to_json_binary(&response)
+}
+
+pub fn query_role_managers(
+    deps: Deps,
+    role: String,
+) -> StdResult<Binary> {
+    let managers: Vec<Addr> = ROLE_MANAGERS
+        .prefix(role)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&managers)
+}
+
+pub fn query_collection_owners(
+    deps: Deps,
+    collection: String,
+) -> StdResult<Binary> {
+    let owners: Vec<Addr> = COLLECTION_OWNERS
+        .prefix(collection)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&owners)
+}
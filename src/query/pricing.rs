@@ -0,0 +1,37 @@
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Deps, Order, StdResult,
+};
+
+use crate::state::{FeeBalance, ACCEPTED_CW20, COLLECTED_FEES, COLLECTION_PRICING, DOCUMENT_DEPOSITS};
+
+pub fn query_collection_pricing(
+    deps: Deps,
+    collection: String,
+) -> StdResult<Binary> {
+    let pricing = COLLECTION_PRICING.may_load(deps.storage, collection)?;
+    to_json_binary(&pricing)
+}
+
+pub fn query_document_deposit(
+    deps: Deps,
+    collection: String,
+    document: String,
+) -> StdResult<Binary> {
+    let deposit = DOCUMENT_DEPOSITS.may_load(deps.storage, (collection, document))?;
+    to_json_binary(&deposit)
+}
+
+pub fn query_collected_fees(deps: Deps) -> StdResult<Binary> {
+    let fees: Vec<FeeBalance> = COLLECTED_FEES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, balance)| balance))
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&fees)
+}
+
+pub fn query_accepted_cw20(deps: Deps) -> StdResult<Binary> {
+    let contracts: Vec<Addr> = ACCEPTED_CW20
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&contracts)
+}
@@ -0,0 +1,63 @@
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, StdResult,
+};
+
+use crate::msg::UserUsageResponse;
+use crate::state::{Quota, Usage, COLLECTION_QUOTAS, COLLECTION_USAGE, GLOBAL_QUOTA, USER_USAGE};
+
+pub fn query_collection_quota(
+    deps: Deps,
+    collection: String,
+) -> StdResult<Binary> {
+    let quota = COLLECTION_QUOTAS.may_load(deps.storage, collection)?;
+    to_json_binary(&quota)
+}
+
+pub fn query_global_quota(deps: Deps) -> StdResult<Binary> {
+    to_json_binary(&GLOBAL_QUOTA.may_load(deps.storage)?)
+}
+
+pub fn query_user_usage(
+    deps: Deps,
+    user: String,
+    collection: Option<String>,
+) -> StdResult<Binary> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let usage = USER_USAGE.may_load(deps.storage, user_addr.clone())?.unwrap_or_default();
+
+    // Pair each applicable quota with the usage it is measured against
+    let mut limits: Vec<(Quota, Usage)> = Vec::new();
+    if let Some(quota) = GLOBAL_QUOTA.may_load(deps.storage)? {
+        limits.push((quota, usage.clone()));
+    }
+    let collection_usage = match collection {
+        Some(collection) => {
+            let collection_usage = COLLECTION_USAGE
+                .may_load(deps.storage, (user_addr, collection.clone()))?
+                .unwrap_or_default();
+            if let Some(quota) = COLLECTION_QUOTAS.may_load(deps.storage, collection)? {
+                limits.push((quota, collection_usage.clone()));
+            }
+            Some(collection_usage)
+        }
+        None => None,
+    };
+
+    let remaining_documents = limits.iter()
+        .filter_map(|(quota, usage)| quota.max_documents.map(|max| max.saturating_sub(usage.documents)))
+        .min();
+    let remaining_bytes = limits.iter()
+        .filter_map(|(quota, usage)| quota.max_bytes.map(|max| max.saturating_sub(usage.bytes)))
+        .min();
+    let max_document_size = limits.iter()
+        .filter_map(|(quota, _)| quota.max_document_size)
+        .min();
+
+    to_json_binary(&UserUsageResponse {
+        usage,
+        collection_usage,
+        remaining_documents,
+        remaining_bytes,
+        max_document_size,
+    })
+}
@@ -0,0 +1,66 @@
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, Env, StdResult,
+};
+
+use crate::execute::rate_limit::{bucket_state, retry_after};
+use crate::msg::RateLimitStatusResponse;
+use crate::state::{ADMIN, COLLECTION_RATE_LIMITS, RATE_LIMIT_BUCKETS};
+use super::has_active_role;
+
+pub fn query_rate_limit(
+    deps: Deps,
+    collection: String,
+) -> StdResult<Binary> {
+    let limit = COLLECTION_RATE_LIMITS.may_load(deps.storage, collection)?;
+    to_json_binary(&limit)
+}
+
+pub fn query_rate_limit_status(
+    deps: Deps,
+    env: Env,
+    collection: String,
+    user: String,
+) -> StdResult<Binary> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let limit = match COLLECTION_RATE_LIMITS.may_load(deps.storage, collection.clone())? {
+        Some(limit) => limit,
+        None => {
+            return to_json_binary(&RateLimitStatusResponse {
+                limit: None,
+                exempt: false,
+                remaining_writes: None,
+                retry_after: None,
+            });
+        }
+    };
+
+    let mut exempt = ADMIN.may_load(deps.storage)?.as_ref() == Some(&user_addr);
+    for role in &limit.exempt_roles {
+        exempt = exempt || has_active_role(deps, &user_addr, role, Some(env.block.time))?;
+    }
+    if exempt {
+        return to_json_binary(&RateLimitStatusResponse {
+            limit: Some(limit),
+            exempt,
+            remaining_writes: None,
+            retry_after: None,
+        });
+    }
+
+    let bucket = RATE_LIMIT_BUCKETS.may_load(deps.storage, (collection, user_addr))?;
+    let state = bucket_state(&limit, bucket, limit.window.now(&env));
+    let used = state.full_at - state.now;
+    let remaining = (state.capacity.saturating_sub(used) / state.cost).u128() as u32;
+    let retry_after = if remaining == 0 {
+        Some(retry_after(limit.max_writes, &state))
+    } else {
+        None
+    };
+
+    to_json_binary(&RateLimitStatusResponse {
+        limit: Some(limit),
+        exempt,
+        remaining_writes: Some(remaining),
+        retry_after,
+    })
+}
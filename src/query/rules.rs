@@ -0,0 +1,13 @@
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, StdResult,
+};
+
+use crate::state::COLLECTION_RULES;
+
+pub fn query_collection_rules(
+    deps: Deps,
+    collection: String,
+) -> StdResult<Binary> {
+    let rules = COLLECTION_RULES.may_load(deps.storage, collection)?;
+    to_json_binary(&rules)
+}
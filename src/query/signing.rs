@@ -0,0 +1,48 @@
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, StdResult, Order,
+};
+use cw_storage_plus::Bound;
+
+use crate::msg::SignatureRequestListResponse;
+use crate::state::{SignatureRequest, SIGNATURE_REQUESTS};
+
+pub fn query_signature_request(
+    deps: Deps,
+    request_id: u64,
+) -> StdResult<Binary> {
+    let request = SIGNATURE_REQUESTS.load(deps.storage, request_id)?;
+    to_json_binary(&request)
+}
+
+pub fn query_list_signature_requests(
+    deps: Deps,
+    collection: String,
+    document: String,
+    limit: Option<u32>,
+    start_after: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(30) as usize;
+    let start = start_after.map(Bound::exclusive);
+    
+    let requests: Vec<SignatureRequest> = SIGNATURE_REQUESTS
+        .idx
+        .document
+        .prefix((collection, document))
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, request)| request))
+        .collect::<StdResult<Vec<_>>>()?;
+    
+    let next_start_after = if requests.len() == limit {
+        requests.last().map(|r| r.id)
+    } else {
+        None
+    };
+    
+    let response = SignatureRequestListResponse {
+        requests,
+        next_start_after,
+    };
+    
+    to_json_binary(&response)
+}
@@ -1,11 +1,13 @@
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, Deps, StdResult, Order,
+    to_json_binary, Binary, Deps, Env, StdError, StdResult, Order,
 };
 use cw_storage_plus::Bound;
 use std::collections::HashSet;
 
 use crate::msg::{DocumentStats, CollectionInfo, CollectionListResponse, SearchResult, SearchResponse};
-use crate::state::{Document, DOCUMENTS};
+use crate::state::{COLLECTION_FIELD_RULES, DOCUMENTS};
+use super::fields::redact_with_rules;
+use super::state_allows_read;
 
 pub fn query_document_stats(
     deps: Deps,
@@ -53,10 +55,13 @@ pub fn query_list_collections(
     // Get all documents and group by collection
     for item in DOCUMENTS.range(deps.storage, None, None, Order::Ascending) {
         let ((coll, _), doc) = item?;
+        if start_after.as_ref().is_some_and(|start| &coll <= start) {
+            continue;
+        }
         
         if current_collection.as_ref() != Some(&coll) {
             // Save previous collection if exists
-            if let Some(prev_coll) = current_collection {
+            if let Some(prev_coll) = current_collection.take() {
                 if collections.len() >= limit {
                     break;
                 }
@@ -106,12 +111,17 @@ pub fn query_list_collections(
 
 pub fn query_search_documents(
     deps: Deps,
+    env: Env,
     collection: String,
     query: String,
     limit: Option<u32>,
     start_after: Option<String>,
+    viewer: Option<String>,
 ) -> StdResult<Binary> {
     let limit = limit.unwrap_or(30) as usize;
+    let viewer = viewer.map(|v| deps.api.addr_validate(&v)).transpose()?;
+    let field_rules = COLLECTION_FIELD_RULES.may_load(deps.storage, collection.clone())?
+        .unwrap_or_default();
     let start = start_after.as_ref().map(|s| Bound::exclusive((collection.clone(), s.clone())));
     let end = Bound::exclusive((format!("{}~", collection), String::new()));
     
@@ -122,7 +132,13 @@ pub fn query_search_documents(
         .map_err(|e| StdError::generic_err(format!("Invalid query JSON: {}", e)))?;
     
     for item in DOCUMENTS.range(deps.storage, start, Some(end), Order::Ascending) {
-        let ((_, doc_id), doc) = item?;
+        let ((_, doc_id), mut doc) = item?;
+        if !state_allows_read(deps, &collection, &doc, viewer.as_ref(), env.block.time)? {
+            continue;
+        }
+        
+        // Never match on fields the viewer cannot read
+        redact_with_rules(deps, &field_rules, &mut doc, viewer.as_ref(), env.block.time)?;
         
         // Parse document data as JSON
         let doc_value: serde_json::Value = serde_json::from_str(&doc.data)
@@ -133,7 +149,7 @@ pub fn query_search_documents(
         
         if !matching_fields.is_empty() {
             results.push(SearchResult {
-                document_id,
+                document_id: doc_id,
                 matching_fields,
                 relevance_score: relevance,
             });
@@ -0,0 +1,13 @@
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, StdResult,
+};
+
+use crate::state::COLLECTION_WORKFLOWS;
+
+pub fn query_collection_workflow(
+    deps: Deps,
+    collection: String,
+) -> StdResult<Binary> {
+    let workflow = COLLECTION_WORKFLOWS.may_load(deps.storage, collection)?;
+    to_json_binary(&workflow)
+}
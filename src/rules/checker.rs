@@ -0,0 +1,149 @@
+use cosmwasm_std::{StdError, StdResult};
+
+use super::{BinaryOp, Expr, Rule, UnaryOp, MAX_RULE_DEPTH};
+
+// Static types. `Dyn` is anything read out of document JSON, whose shape is
+// only known at runtime. Maps only ever come from document JSON, so they are
+// always `Dyn`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Type {
+    Null,
+    Bool,
+    Int,
+    Str,
+    List,
+    Dyn,
+    Request,
+    Resource,
+}
+
+fn error(msg: String) -> StdError {
+    StdError::generic_err(format!("Rule type error: {}", msg))
+}
+
+pub fn check_rule(rule: &Rule) -> StdResult<()> {
+    match check(&rule.condition, 1)? {
+        Type::Bool | Type::Dyn => Ok(()),
+        other => Err(error(format!("condition must be a bool, found {:?}", other))),
+    }
+}
+
+// The parser only bounds recursion, left-associative chains such as
+// `a && b && c` still build deep trees. Stored rules must stay well below the
+// JSON nesting limit (two levels per node) or they can't be loaded again.
+fn check(expr: &Expr, depth: u32) -> StdResult<Type> {
+    if depth > MAX_RULE_DEPTH {
+        return Err(error(format!("expression is nested deeper than {} levels", MAX_RULE_DEPTH)));
+    }
+    let check = |expr: &Expr| check(expr, depth + 1);
+    match expr {
+        Expr::Null => Ok(Type::Null),
+        Expr::Bool(_) => Ok(Type::Bool),
+        Expr::Int(_) => Ok(Type::Int),
+        Expr::Str(_) => Ok(Type::Str),
+        Expr::List(items) => {
+            for item in items {
+                check(item)?;
+            }
+            Ok(Type::List)
+        }
+        Expr::Var(name) => match name.as_str() {
+            "request" => Ok(Type::Request),
+            "resource" => Ok(Type::Resource),
+            _ => Err(error(format!("unknown variable '{}'", name))),
+        },
+        Expr::Member(target, field) => {
+            let target = check(target)?;
+            match (target, field.as_str()) {
+                (Type::Request, "auth") => Ok(Type::Str),
+                (Type::Request, "roles") => Ok(Type::List),
+                (Type::Request, "data") => Ok(Type::Dyn),
+                (Type::Request, "time") => Ok(Type::Int),
+                (Type::Resource, "owner") => Ok(Type::Str),
+                (Type::Resource, "data") => Ok(Type::Dyn),
+                (Type::Resource, "status") => Ok(Type::Dyn),
+                (Type::Resource, "created_at") | (Type::Resource, "updated_at") => Ok(Type::Int),
+                (Type::Dyn, _) => Ok(Type::Dyn),
+                (target, field) => Err(error(format!("{:?} has no field '{}'", target, field))),
+            }
+        }
+        Expr::Index(target, index) => {
+            let target = check(target)?;
+            let index = check(index)?;
+            match (target, index) {
+                (Type::List, Type::Int) | (Type::List, Type::Dyn) => Ok(Type::Dyn),
+                (Type::Dyn, Type::Int) | (Type::Dyn, Type::Str) | (Type::Dyn, Type::Dyn) => Ok(Type::Dyn),
+                (target, index) => Err(error(format!("cannot index {:?} with {:?}", target, index))),
+            }
+        }
+        Expr::Call(name, args) => {
+            let args = args.iter().map(&check).collect::<StdResult<Vec<_>>>()?;
+            match (name.as_str(), args.as_slice()) {
+                ("hasRole", [Type::Str]) | ("hasRole", [Type::Dyn]) => Ok(Type::Bool),
+                (name, args) => Err(error(format!("unknown function {}({:?})", name, args))),
+            }
+        }
+        Expr::Method(target, name, args) => {
+            let target = check(target)?;
+            let args = args.iter().map(&check).collect::<StdResult<Vec<_>>>()?;
+            let sized = matches!(target, Type::Str | Type::List | Type::Dyn);
+            let searchable = matches!(target, Type::Str | Type::List | Type::Dyn);
+            let keyed = target == Type::Dyn;
+            match (name.as_str(), args.len()) {
+                ("size", 0) if sized => Ok(Type::Int),
+                ("contains", 1) if searchable => Ok(Type::Bool),
+                ("keys", 0) if keyed => Ok(Type::List),
+                (name, _) => Err(error(format!("{:?} has no method {}({:?})", target, name, args))),
+            }
+        }
+        Expr::Unary(op, operand) => {
+            let operand = check(operand)?;
+            match (op, operand) {
+                (UnaryOp::Not, Type::Bool) | (UnaryOp::Not, Type::Dyn) => Ok(Type::Bool),
+                (UnaryOp::Neg, Type::Int) | (UnaryOp::Neg, Type::Dyn) => Ok(Type::Int),
+                (op, operand) => Err(error(format!("cannot apply {:?} to {:?}", op, operand))),
+            }
+        }
+        Expr::Binary(op, lhs, rhs) => {
+            let lhs = check(lhs)?;
+            let rhs = check(rhs)?;
+            let dynamic = lhs == Type::Dyn || rhs == Type::Dyn;
+            match op {
+                BinaryOp::And | BinaryOp::Or => {
+                    let boolish = |t: Type| t == Type::Bool || t == Type::Dyn;
+                    if boolish(lhs) && boolish(rhs) {
+                        Ok(Type::Bool)
+                    } else {
+                        Err(error(format!("cannot apply {:?} to {:?} and {:?}", op, lhs, rhs)))
+                    }
+                }
+                BinaryOp::Eq | BinaryOp::Ne => Ok(Type::Bool),
+                BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => {
+                    if dynamic || (lhs == rhs && matches!(lhs, Type::Int | Type::Str)) {
+                        Ok(Type::Bool)
+                    } else {
+                        Err(error(format!("cannot compare {:?} with {:?}", lhs, rhs)))
+                    }
+                }
+                BinaryOp::In => {
+                    if matches!(rhs, Type::List | Type::Str | Type::Dyn) {
+                        Ok(Type::Bool)
+                    } else {
+                        Err(error(format!("cannot test membership in {:?}", rhs)))
+                    }
+                }
+                BinaryOp::Add => match (lhs, rhs) {
+                    (Type::Int, Type::Int) => Ok(Type::Int),
+                    (Type::Str, Type::Str) => Ok(Type::Str),
+                    _ if dynamic => Ok(Type::Dyn),
+                    _ => Err(error(format!("cannot add {:?} and {:?}", lhs, rhs))),
+                },
+                BinaryOp::Sub => match (lhs, rhs) {
+                    (Type::Int, Type::Int) => Ok(Type::Int),
+                    _ if dynamic => Ok(Type::Int),
+                    _ => Err(error(format!("cannot subtract {:?} from {:?}", rhs, lhs))),
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use std::collections::BTreeMap;
+
+use super::{BinaryOp, Expr, RuleInput, UnaryOp};
+
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Str(String),
+    List(Vec<Value>),
+    Map(BTreeMap<String, Value>),
+}
+
+// Any runtime failure denies the request, so errors carry no detail
+struct Fault;
+
+type EvalResult = Result<Value, Fault>;
+
+pub struct Budget {
+    remaining: u64,
+}
+
+impl Budget {
+    pub fn new(steps: u64) -> Self {
+        Self { remaining: steps }
+    }
+
+    fn charge(&mut self, steps: u64) -> Result<(), Fault> {
+        self.remaining = self.remaining.checked_sub(steps).ok_or(Fault)?;
+        Ok(())
+    }
+}
+
+pub fn eval_condition(expr: &Expr, input: &RuleInput, budget: &mut Budget) -> bool {
+    matches!(eval(expr, input, budget), Ok(Value::Bool(true)))
+}
+
+fn eval(expr: &Expr, input: &RuleInput, budget: &mut Budget) -> EvalResult {
+    budget.charge(1)?;
+    match expr {
+        Expr::Null => Ok(Value::Null),
+        Expr::Bool(b) => Ok(Value::Bool(*b)),
+        Expr::Int(i) => Ok(Value::Int(*i)),
+        Expr::Str(s) => Ok(Value::Str(s.clone())),
+        Expr::List(items) => {
+            let items = items.iter()
+                .map(|item| eval(item, input, budget))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Value::List(items))
+        }
+        Expr::Var(name) => match name.as_str() {
+            "request" => request_value(input, budget),
+            "resource" => resource_value(input, budget),
+            _ => Err(Fault),
+        },
+        Expr::Member(target, field) => match eval(target, input, budget)? {
+            Value::Map(mut map) => Ok(map.remove(field).unwrap_or(Value::Null)),
+            _ => Err(Fault),
+        },
+        Expr::Index(target, index) => {
+            let target = eval(target, input, budget)?;
+            let index = eval(index, input, budget)?;
+            match (target, index) {
+                (Value::List(mut items), Value::Int(i)) => {
+                    let i = usize::try_from(i).map_err(|_| Fault)?;
+                    if i < items.len() {
+                        Ok(items.swap_remove(i))
+                    } else {
+                        Err(Fault)
+                    }
+                }
+                (Value::Map(mut map), Value::Str(key)) => Ok(map.remove(&key).unwrap_or(Value::Null)),
+                _ => Err(Fault),
+            }
+        }
+        Expr::Call(name, args) => {
+            let args = args.iter()
+                .map(|arg| eval(arg, input, budget))
+                .collect::<Result<Vec<_>, _>>()?;
+            match (name.as_str(), args.as_slice()) {
+                ("hasRole", [Value::Str(role)]) => {
+                    budget.charge(input.roles.len() as u64)?;
+                    Ok(Value::Bool(input.roles.contains(role)))
+                }
+                _ => Err(Fault),
+            }
+        }
+        Expr::Method(target, name, args) => {
+            let target = eval(target, input, budget)?;
+            let args = args.iter()
+                .map(|arg| eval(arg, input, budget))
+                .collect::<Result<Vec<_>, _>>()?;
+            match (name.as_str(), target, args.as_slice()) {
+                ("size", Value::Str(s), []) => Ok(Value::Int(s.chars().count() as i64)),
+                ("size", Value::List(items), []) => Ok(Value::Int(items.len() as i64)),
+                ("size", Value::Map(map), []) => Ok(Value::Int(map.len() as i64)),
+                ("contains", Value::Str(s), [Value::Str(needle)]) => {
+                    budget.charge(s.len() as u64)?;
+                    Ok(Value::Bool(s.contains(needle.as_str())))
+                }
+                ("contains", Value::List(items), [needle]) => {
+                    budget.charge(items.len() as u64)?;
+                    Ok(Value::Bool(items.contains(needle)))
+                }
+                ("keys", Value::Map(map), []) => {
+                    budget.charge(map.len() as u64)?;
+                    Ok(Value::List(map.into_keys().map(Value::Str).collect()))
+                }
+                _ => Err(Fault),
+            }
+        }
+        Expr::Unary(op, operand) => match (op, eval(operand, input, budget)?) {
+            (UnaryOp::Not, Value::Bool(b)) => Ok(Value::Bool(!b)),
+            (UnaryOp::Neg, Value::Int(i)) => i.checked_neg().map(Value::Int).ok_or(Fault),
+            _ => Err(Fault),
+        },
+        Expr::Binary(BinaryOp::And, lhs, rhs) => match eval(lhs, input, budget)? {
+            Value::Bool(false) => Ok(Value::Bool(false)),
+            Value::Bool(true) => match eval(rhs, input, budget)? {
+                Value::Bool(b) => Ok(Value::Bool(b)),
+                _ => Err(Fault),
+            },
+            _ => Err(Fault),
+        },
+        Expr::Binary(BinaryOp::Or, lhs, rhs) => match eval(lhs, input, budget)? {
+            Value::Bool(true) => Ok(Value::Bool(true)),
+            Value::Bool(false) => match eval(rhs, input, budget)? {
+                Value::Bool(b) => Ok(Value::Bool(b)),
+                _ => Err(Fault),
+            },
+            _ => Err(Fault),
+        },
+        Expr::Binary(op, lhs, rhs) => {
+            let lhs = eval(lhs, input, budget)?;
+            let rhs = eval(rhs, input, budget)?;
+            binary(op, lhs, rhs, budget)
+        }
+    }
+}
+
+fn binary(op: &BinaryOp, lhs: Value, rhs: Value, budget: &mut Budget) -> EvalResult {
+    match op {
+        BinaryOp::Eq | BinaryOp::Ne => {
+            budget.charge(value_size(&lhs).min(value_size(&rhs)))?;
+            let equal = lhs == rhs;
+            Ok(Value::Bool(if *op == BinaryOp::Eq { equal } else { !equal }))
+        }
+        BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => {
+            let ordering = match (&lhs, &rhs) {
+                (Value::Int(a), Value::Int(b)) => a.cmp(b),
+                (Value::Str(a), Value::Str(b)) => {
+                    budget.charge(a.len().min(b.len()) as u64)?;
+                    a.cmp(b)
+                }
+                _ => return Err(Fault),
+            };
+            let result = match op {
+                BinaryOp::Lt => ordering.is_lt(),
+                BinaryOp::Le => ordering.is_le(),
+                BinaryOp::Gt => ordering.is_gt(),
+                _ => ordering.is_ge(),
+            };
+            Ok(Value::Bool(result))
+        }
+        BinaryOp::In => match (lhs, rhs) {
+            (needle, Value::List(items)) => {
+                budget.charge(items.len() as u64)?;
+                Ok(Value::Bool(items.contains(&needle)))
+            }
+            (Value::Str(key), Value::Map(map)) => Ok(Value::Bool(map.contains_key(&key))),
+            (Value::Str(needle), Value::Str(s)) => {
+                budget.charge(s.len() as u64)?;
+                Ok(Value::Bool(s.contains(needle.as_str())))
+            }
+            _ => Err(Fault),
+        },
+        BinaryOp::Add => match (lhs, rhs) {
+            (Value::Int(a), Value::Int(b)) => a.checked_add(b).map(Value::Int).ok_or(Fault),
+            (Value::Str(a), Value::Str(b)) => {
+                budget.charge((a.len() + b.len()) as u64)?;
+                Ok(Value::Str(a + &b))
+            }
+            _ => Err(Fault),
+        },
+        BinaryOp::Sub => match (lhs, rhs) {
+            (Value::Int(a), Value::Int(b)) => a.checked_sub(b).map(Value::Int).ok_or(Fault),
+            _ => Err(Fault),
+        },
+        BinaryOp::And | BinaryOp::Or => Err(Fault),
+    }
+}
+
+// Rough cost of touching a value, used to charge comparisons
+fn value_size(value: &Value) -> u64 {
+    match value {
+        Value::Str(s) => s.len() as u64,
+        Value::List(items) => items.len() as u64,
+        Value::Map(map) => map.len() as u64,
+        _ => 1,
+    }
+}
+
+fn request_value(input: &RuleInput, budget: &mut Budget) -> EvalResult {
+    let mut request = BTreeMap::new();
+    request.insert("auth".to_string(), Value::Str(input.sender.to_string()));
+    request.insert(
+        "roles".to_string(),
+        Value::List(input.roles.iter().cloned().map(Value::Str).collect()),
+    );
+    let data = match input.data {
+        Some(data) => parse_json(data, budget)?,
+        None => Value::Null,
+    };
+    request.insert("data".to_string(), data);
+    let time = match input.time {
+        Some(time) => Value::Int(i64::try_from(time.seconds()).map_err(|_| Fault)?),
+        None => Value::Null,
+    };
+    request.insert("time".to_string(), time);
+    Ok(Value::Map(request))
+}
+
+fn resource_value(input: &RuleInput, budget: &mut Budget) -> EvalResult {
+    let doc = match input.resource {
+        Some(doc) => doc,
+        None => return Ok(Value::Null),
+    };
+    let mut resource = BTreeMap::new();
+    resource.insert("owner".to_string(), Value::Str(doc.owner.to_string()));
+    resource.insert("data".to_string(), parse_json(&doc.data, budget)?);
+    resource.insert(
+        "status".to_string(),
+        doc.status.clone().map(Value::Str).unwrap_or(Value::Null),
+    );
+    let created_at = i64::try_from(doc.created_at.seconds()).map_err(|_| Fault)?;
+    let updated_at = i64::try_from(doc.updated_at.seconds()).map_err(|_| Fault)?;
+    resource.insert("created_at".to_string(), Value::Int(created_at));
+    resource.insert("updated_at".to_string(), Value::Int(updated_at));
+    Ok(Value::Map(resource))
+}
+
+fn parse_json(data: &str, budget: &mut Budget) -> EvalResult {
+    // Parsing cost scales with the document size
+    budget.charge(data.len() as u64 / 32 + 1)?;
+    let json: serde_json::Value = serde_json::from_str(data).map_err(|_| Fault)?;
+    from_json(json)
+}
+
+fn from_json(json: serde_json::Value) -> EvalResult {
+    match json {
+        serde_json::Value::Null => Ok(Value::Null),
+        serde_json::Value::Bool(b) => Ok(Value::Bool(b)),
+        // Only integers, floats would make evaluation non-deterministic. Other
+        // numbers read as null so they don't make the whole document unreadable.
+        serde_json::Value::Number(n) => Ok(n.as_i64().map(Value::Int).unwrap_or(Value::Null)),
+        serde_json::Value::String(s) => Ok(Value::Str(s)),
+        serde_json::Value::Array(items) => {
+            let items = items.into_iter().map(from_json).collect::<Result<Vec<_>, _>>()?;
+            Ok(Value::List(items))
+        }
+        serde_json::Value::Object(map) => {
+            let map = map.into_iter()
+                .map(|(k, v)| Ok((k, from_json(v)?)))
+                .collect::<Result<BTreeMap<_, _>, Fault>>()?;
+            Ok(Value::Map(map))
+        }
+    }
+}
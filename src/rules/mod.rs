@@ -0,0 +1,132 @@
+// Firestore-style security rules evaluated on chain.
+//
+// A rule set is a list of statements of the form
+//
+//     allow read, update: if request.auth == resource.owner && request.data.title.size() < 100;
+//
+// Available actions are `read`, `create`, `update`, `delete`, `share` and
+// `write` (shorthand for create, update and delete). Expressions can use:
+//
+//     request.auth      sender address (string)
+//     request.roles     roles granted to the sender (list)
+//     request.data      incoming data, the merged fields for updates (null for read/delete)
+//     request.time      block time in seconds (int)
+//     resource.owner    owner of the existing document (string)
+//     resource.data     existing document data (map)
+//     resource.status   lifecycle state of the existing document
+//     resource.created_at / resource.updated_at  (int seconds)
+//
+// `resource` itself is null when the document does not exist yet. Values
+// support `==`, `!=`, `<`, `<=`, `>`, `>=`, `in`, `+`, `-`, `&&`, `||`, `!`,
+// indexing with `[]`, the methods `size()`, `contains(x)` and `keys()`, and
+// the function `hasRole(name)`. Numbers are 64-bit integers only so the
+// interpreter stays deterministic; floats and integers outside the i64 range
+// in document data read as null.
+//
+// Rules are parsed and type checked when uploaded and stored as an AST. The
+// interpreter runs with a fixed step budget; running out of budget or hitting
+// a runtime error (e.g. reading a field of null) denies the request.
+
+use cosmwasm_std::{Addr, StdResult, Timestamp};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::state::Document;
+
+mod checker;
+mod interpreter;
+mod parser;
+
+pub const MAX_RULES_SOURCE_LEN: usize = 8 * 1024;
+pub const MAX_RULE_NODES: u32 = 512;
+pub const MAX_RULE_DEPTH: u32 = 32;
+pub const MAX_RULE_STEPS: u64 = 10_000;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum UnaryOp {
+    Not,
+    Neg,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum BinaryOp {
+    And,
+    Or,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    In,
+    Add,
+    Sub,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum Expr {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Str(String),
+    List(Vec<Expr>),
+    Var(String),                             // `request` or `resource`
+    Member(Box<Expr>, String),               // a.b
+    Index(Box<Expr>, Box<Expr>),             // a[b]
+    Call(String, Vec<Expr>),                 // f(x)
+    Method(Box<Expr>, String, Vec<Expr>),    // a.f(x)
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Rule {
+    pub actions: Vec<String>,
+    pub condition: Expr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollectionRules {
+    pub source: String,
+    pub rules: Vec<Rule>,
+}
+
+// Everything a rule can observe about the request being authorized
+pub struct RuleInput<'a> {
+    pub sender: &'a Addr,
+    pub roles: &'a [String],
+    pub data: Option<&'a str>,
+    pub time: Option<Timestamp>,
+    pub resource: Option<&'a Document>,
+}
+
+// Parses and type checks rule source before it is stored
+pub fn compile(source: &str) -> StdResult<CollectionRules> {
+    let rules = parser::parse(source)?;
+    for rule in &rules {
+        checker::check_rule(rule)?;
+    }
+    Ok(CollectionRules {
+        source: source.to_string(),
+        rules,
+    })
+}
+
+impl CollectionRules {
+    pub fn covers(&self, action: &str) -> bool {
+        self.rules.iter().any(|rule| rule.actions.iter().any(|a| a == action))
+    }
+
+    // Some(true) if any statement for the action allows it, Some(false) if
+    // statements exist but none allow it, None if the action is not covered.
+    pub fn evaluate(&self, action: &str, input: &RuleInput) -> Option<bool> {
+        if !self.covers(action) {
+            return None;
+        }
+        let mut budget = interpreter::Budget::new(MAX_RULE_STEPS);
+        let allowed = self.rules.iter()
+            .filter(|rule| rule.actions.iter().any(|a| a == action))
+            .any(|rule| interpreter::eval_condition(&rule.condition, input, &mut budget));
+        Some(allowed)
+    }
+}
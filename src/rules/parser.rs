@@ -0,0 +1,388 @@
+use cosmwasm_std::{StdError, StdResult};
+
+use super::{BinaryOp, Expr, Rule, UnaryOp, MAX_RULES_SOURCE_LEN, MAX_RULE_DEPTH, MAX_RULE_NODES};
+
+const ACTIONS: [&str; 5] = ["read", "create", "update", "delete", "share"];
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Ident(String),
+    Int(i64),
+    Str(String),
+    Punct(&'static str),
+}
+
+// Longer operators first so "<=" is not read as "<"
+const PUNCTUATION: [&str; 19] = [
+    "&&", "||", "==", "!=", "<=", ">=", "<", ">", "!", "+", "-",
+    "(", ")", "[", "]", ",", ".", ":", ";",
+];
+
+fn error(pos: usize, msg: &str) -> StdError {
+    StdError::generic_err(format!("Rule parse error at {}: {}", pos, msg))
+}
+
+fn tokenize(source: &str) -> StdResult<Vec<(usize, Token)>> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+
+    'outer: while pos < bytes.len() {
+        let c = bytes[pos];
+        if c.is_ascii_whitespace() {
+            pos += 1;
+            continue;
+        }
+        // Line comments
+        if source[pos..].starts_with("//") {
+            while pos < bytes.len() && bytes[pos] != b'\n' {
+                pos += 1;
+            }
+            continue;
+        }
+        if c.is_ascii_alphabetic() || c == b'_' {
+            let start = pos;
+            while pos < bytes.len() && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'_') {
+                pos += 1;
+            }
+            tokens.push((start, Token::Ident(source[start..pos].to_string())));
+            continue;
+        }
+        if c.is_ascii_digit() {
+            let start = pos;
+            while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+                pos += 1;
+            }
+            let value = source[start..pos].parse::<i64>()
+                .map_err(|_| error(start, "integer out of range"))?;
+            tokens.push((start, Token::Int(value)));
+            continue;
+        }
+        if c == b'\'' || c == b'"' {
+            let quote = c as char;
+            let start = pos;
+            let mut value = String::new();
+            pos += 1;
+            loop {
+                let ch = source[pos..].chars().next()
+                    .ok_or_else(|| error(start, "unterminated string"))?;
+                pos += ch.len_utf8();
+                match ch {
+                    '\\' => {
+                        let escaped = source[pos..].chars().next()
+                            .ok_or_else(|| error(start, "unterminated string"))?;
+                        pos += escaped.len_utf8();
+                        match escaped {
+                            'n' => value.push('\n'),
+                            't' => value.push('\t'),
+                            '\\' | '\'' | '"' => value.push(escaped),
+                            _ => return Err(error(pos, "invalid escape sequence")),
+                        }
+                    }
+                    ch if ch == quote => break,
+                    ch => value.push(ch),
+                }
+            }
+            tokens.push((start, Token::Str(value)));
+            continue;
+        }
+        for punct in PUNCTUATION {
+            if source[pos..].starts_with(punct) {
+                tokens.push((pos, Token::Punct(punct)));
+                pos += punct.len();
+                continue 'outer;
+            }
+        }
+        return Err(error(pos, "unexpected character"));
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    end: usize,
+    nodes: u32,
+    depth: u32,
+}
+
+pub fn parse(source: &str) -> StdResult<Vec<Rule>> {
+    if source.len() > MAX_RULES_SOURCE_LEN {
+        return Err(StdError::generic_err(format!("Rules exceed max length of {} bytes", MAX_RULES_SOURCE_LEN)));
+    }
+
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        pos: 0,
+        end: source.len(),
+        nodes: 0,
+        depth: 0,
+    };
+
+    let mut rules = Vec::new();
+    while parser.peek().is_some() {
+        rules.push(parser.rule()?);
+    }
+    if rules.is_empty() {
+        return Err(error(0, "expected at least one allow statement"));
+    }
+    Ok(rules)
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, t)| t)
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens.get(self.pos).map(|(p, _)| *p).unwrap_or(self.end)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).map(|(_, t)| t.clone());
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, punct: &str) -> bool {
+        match self.peek() {
+            Some(Token::Punct(p)) if *p == punct => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect(&mut self, punct: &str) -> StdResult<()> {
+        if self.eat(punct) {
+            Ok(())
+        } else {
+            Err(error(self.offset(), &format!("expected '{}'", punct)))
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> StdResult<()> {
+        match self.next() {
+            Some(Token::Ident(ident)) if ident == keyword => Ok(()),
+            _ => Err(error(self.offset(), &format!("expected '{}'", keyword))),
+        }
+    }
+
+    fn ident(&mut self) -> StdResult<String> {
+        let offset = self.offset();
+        match self.next() {
+            Some(Token::Ident(ident)) => Ok(ident),
+            _ => Err(error(offset, "expected identifier")),
+        }
+    }
+
+    fn node(&mut self) -> StdResult<()> {
+        self.nodes += 1;
+        if self.nodes > MAX_RULE_NODES {
+            return Err(error(self.offset(), "rules are too large"));
+        }
+        Ok(())
+    }
+
+    // allow <action>[, <action>]*: if <expr>;
+    fn rule(&mut self) -> StdResult<Rule> {
+        self.expect_keyword("allow")?;
+
+        let mut actions: Vec<String> = Vec::new();
+        loop {
+            let offset = self.offset();
+            let action = self.ident()?;
+            let expanded: Vec<&str> = match action.as_str() {
+                "write" => vec!["create", "update", "delete"],
+                a if ACTIONS.contains(&a) => vec![a],
+                _ => return Err(error(offset, &format!("unknown action '{}'", action))),
+            };
+            for a in expanded {
+                if !actions.iter().any(|existing| existing == a) {
+                    actions.push(a.to_string());
+                }
+            }
+            if !self.eat(",") {
+                break;
+            }
+        }
+
+        self.expect(":")?;
+        self.expect_keyword("if")?;
+        let condition = self.expr()?;
+        self.expect(";")?;
+
+        Ok(Rule { actions, condition })
+    }
+
+    fn expr(&mut self) -> StdResult<Expr> {
+        self.depth += 1;
+        if self.depth > MAX_RULE_DEPTH {
+            return Err(error(self.offset(), "expression is nested too deeply"));
+        }
+        let expr = self.or();
+        self.depth -= 1;
+        expr
+    }
+
+    fn or(&mut self) -> StdResult<Expr> {
+        let mut lhs = self.and()?;
+        while self.eat("||") {
+            self.node()?;
+            let rhs = self.and()?;
+            lhs = Expr::Binary(BinaryOp::Or, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn and(&mut self) -> StdResult<Expr> {
+        let mut lhs = self.comparison()?;
+        while self.eat("&&") {
+            self.node()?;
+            let rhs = self.comparison()?;
+            lhs = Expr::Binary(BinaryOp::And, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn comparison(&mut self) -> StdResult<Expr> {
+        let lhs = self.additive()?;
+        let op = if self.eat("==") {
+            BinaryOp::Eq
+        } else if self.eat("!=") {
+            BinaryOp::Ne
+        } else if self.eat("<=") {
+            BinaryOp::Le
+        } else if self.eat(">=") {
+            BinaryOp::Ge
+        } else if self.eat("<") {
+            BinaryOp::Lt
+        } else if self.eat(">") {
+            BinaryOp::Gt
+        } else if self.peek() == Some(&Token::Ident("in".to_string())) {
+            self.pos += 1;
+            BinaryOp::In
+        } else {
+            return Ok(lhs);
+        };
+        self.node()?;
+        let rhs = self.additive()?;
+        Ok(Expr::Binary(op, Box::new(lhs), Box::new(rhs)))
+    }
+
+    fn additive(&mut self) -> StdResult<Expr> {
+        let mut lhs = self.unary()?;
+        loop {
+            let op = if self.eat("+") {
+                BinaryOp::Add
+            } else if self.eat("-") {
+                BinaryOp::Sub
+            } else {
+                return Ok(lhs);
+            };
+            self.node()?;
+            let rhs = self.unary()?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+    }
+
+    fn unary(&mut self) -> StdResult<Expr> {
+        if self.eat("!") {
+            self.node()?;
+            return Ok(Expr::Unary(UnaryOp::Not, Box::new(self.nested_unary()?)));
+        }
+        if self.eat("-") {
+            self.node()?;
+            return Ok(Expr::Unary(UnaryOp::Neg, Box::new(self.nested_unary()?)));
+        }
+        self.postfix()
+    }
+
+    fn nested_unary(&mut self) -> StdResult<Expr> {
+        self.depth += 1;
+        if self.depth > MAX_RULE_DEPTH {
+            return Err(error(self.offset(), "expression is nested too deeply"));
+        }
+        let expr = self.unary();
+        self.depth -= 1;
+        expr
+    }
+
+    fn postfix(&mut self) -> StdResult<Expr> {
+        let mut expr = self.primary()?;
+        loop {
+            if self.eat(".") {
+                self.node()?;
+                let name = self.ident()?;
+                if self.eat("(") {
+                    let args = self.args()?;
+                    expr = Expr::Method(Box::new(expr), name, args);
+                } else {
+                    expr = Expr::Member(Box::new(expr), name);
+                }
+            } else if self.eat("[") {
+                self.node()?;
+                let index = self.expr()?;
+                self.expect("]")?;
+                expr = Expr::Index(Box::new(expr), Box::new(index));
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    // Arguments after an opening '(' up to and including the closing ')'
+    fn args(&mut self) -> StdResult<Vec<Expr>> {
+        let mut args = Vec::new();
+        if self.eat(")") {
+            return Ok(args);
+        }
+        loop {
+            args.push(self.expr()?);
+            if self.eat(")") {
+                return Ok(args);
+            }
+            self.expect(",")?;
+        }
+    }
+
+    fn primary(&mut self) -> StdResult<Expr> {
+        self.node()?;
+        let offset = self.offset();
+        match self.next() {
+            Some(Token::Int(value)) => Ok(Expr::Int(value)),
+            Some(Token::Str(value)) => Ok(Expr::Str(value)),
+            Some(Token::Ident(ident)) => match ident.as_str() {
+                "true" => Ok(Expr::Bool(true)),
+                "false" => Ok(Expr::Bool(false)),
+                "null" => Ok(Expr::Null),
+                _ if self.eat("(") => {
+                    let args = self.args()?;
+                    Ok(Expr::Call(ident, args))
+                }
+                _ => Ok(Expr::Var(ident)),
+            },
+            Some(Token::Punct("(")) => {
+                let expr = self.expr()?;
+                self.expect(")")?;
+                Ok(expr)
+            }
+            Some(Token::Punct("[")) => {
+                let mut items = Vec::new();
+                if !self.eat("]") {
+                    loop {
+                        items.push(self.expr()?);
+                        if self.eat("]") {
+                            break;
+                        }
+                        self.expect(",")?;
+                    }
+                }
+                Ok(Expr::List(items))
+            }
+            _ => Err(error(offset, "expected expression")),
+        }
+    }
+}
@@ -1,7 +1,11 @@
-use cosmwasm_std::{Addr, Timestamp};
+use cosmwasm_std::{Addr, Api, Empty, Env, StdError, StdResult, Timestamp, Uint128};
 use cw_storage_plus::{Item, Map, MultiIndex, IndexList, IndexedMap, Index};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::msg::ExecuteMsg;
+use crate::rules::CollectionRules;
 
 // Document structure - simple JSON storage
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -10,6 +14,8 @@ pub struct Document {
     pub owner: Addr,
     pub created_at: Timestamp,
     pub updated_at: Timestamp,
+    #[serde(default)]
+    pub status: Option<String>,  // Lifecycle state, set when the collection has a workflow
 }
 
 // Collection path: /collection/document_id
@@ -52,9 +58,53 @@ pub const DOCUMENTS: IndexedMap<DocumentKey, Document, DocumentIndexes> = Indexe
     },
 );
 
-// Contract admin
+// Contract admin, absent once the admin role has been renounced
 pub const ADMIN: Item<Addr> = Item::new("admin");
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AdminProposal {
+    pub new_admin: Addr,
+    pub proposed_at: Timestamp,
+    pub expires_at: Option<Timestamp>,  // None never expires
+}
+
+// Admin transfer awaiting acceptance by the proposed address
+pub const PENDING_ADMIN: Item<AdminProposal> = Item::new("pending_admin");
+
+// M-of-N admin set. While configured, ADMIN is the contract itself and
+// privileged messages only run through approved admin actions.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AdminSet {
+    pub members: Vec<Addr>,
+    pub threshold: u32,
+}
+
+pub const ADMIN_SET: Item<AdminSet> = Item::new("admin_set");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum AdminActionStatus {
+    Pending,
+    Executed,
+    Cancelled,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AdminAction {
+    pub id: u64,
+    pub proposer: Addr,
+    pub msg: ExecuteMsg,
+    pub approvals: Vec<Addr>,
+    pub status: AdminActionStatus,
+    pub created_at: Timestamp,
+    pub expires_at: Option<Timestamp>,
+    pub executed_at: Option<Timestamp>,
+}
+
+pub const ADMIN_ACTION_COUNT: Item<u64> = Item::new("admin_action_count");
+
+// Proposed privileged messages: Map<action_id, action>
+pub const ADMIN_ACTIONS: Map<u64, AdminAction> = Map::new("admin_actions");
+
 // Permission system
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct CollectionPermissions {
@@ -71,6 +121,125 @@ pub enum PermissionLevel {
     AllowList(Vec<String>),          // Only specific users in the list
     DenyList(Vec<String>),           // Anyone except users in the list
     RequireRole(String),             // User must have specific role
+    OwnerOnly,                       // Only the owner of the document being acted on
+    All(Vec<PermissionLevel>),       // Every nested level must allow
+    Any(Vec<PermissionLevel>),       // At least one nested level must allow
+    Not(Box<PermissionLevel>),       // Inverts the nested level
+    Cw4Member {                      // Member of a cw4 group with at least min_weight
+        group_addr: String,
+        min_weight: u64,
+    },
+    Cw721Holder {                    // Holds token_id, or at least min_count tokens (default 1)
+        collection_addr: String,
+        token_id: Option<String>,
+        min_count: Option<u32>,
+    },
+    MinBalance {                     // Holds at least amount of a native denom or cw20 token
+        token: TokenDenom,
+        amount: Uint128,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum TokenDenom {
+    Native(String),  // Bank denom, e.g. "uxion"
+    Cw20(String),    // cw20 contract address
+}
+
+impl TokenDenom {
+    pub fn validate(&self, api: &dyn Api) -> StdResult<()> {
+        match self {
+            TokenDenom::Native(denom) if denom.is_empty() => {
+                Err(StdError::generic_err("Native denom cannot be empty"))
+            }
+            TokenDenom::Native(_) => Ok(()),
+            TokenDenom::Cw20(contract) => api.addr_validate(contract).map(|_| ()),
+        }
+    }
+
+    // Storage key for per-token balances
+    pub fn key(&self) -> String {
+        match self {
+            TokenDenom::Native(denom) => format!("native:{}", denom),
+            TokenDenom::Cw20(contract) => format!("cw20:{}", contract),
+        }
+    }
+}
+
+// Bounds on admin supplied permission expressions so evaluation gas stays small
+pub const MAX_PERMISSION_DEPTH: u32 = 4;
+pub const MAX_PERMISSION_NODES: u32 = 32;
+// Levels that query other contracts, each one costs a cross-contract call
+pub const MAX_PERMISSION_QUERIES: u32 = 4;
+// cw721 contracts cap the page size of Tokens queries
+pub const MAX_NFT_MIN_COUNT: u32 = 100;
+
+impl PermissionLevel {
+    pub fn validate(&self, api: &dyn Api) -> StdResult<()> {
+        let mut nodes = 0u32;
+        let mut queries = 0u32;
+        self.validate_node(api, 1, &mut nodes, &mut queries)
+    }
+
+    fn validate_node(&self, api: &dyn Api, depth: u32, nodes: &mut u32, queries: &mut u32) -> StdResult<()> {
+        *nodes += 1;
+        if depth > MAX_PERMISSION_DEPTH {
+            return Err(StdError::generic_err(format!("Permission expression exceeds max depth of {}", MAX_PERMISSION_DEPTH)));
+        }
+        if *nodes > MAX_PERMISSION_NODES {
+            return Err(StdError::generic_err(format!("Permission expression exceeds max size of {}", MAX_PERMISSION_NODES)));
+        }
+        match self {
+            PermissionLevel::All(levels) | PermissionLevel::Any(levels) => {
+                if levels.is_empty() {
+                    return Err(StdError::generic_err("All/Any permission expressions cannot be empty"));
+                }
+                for level in levels {
+                    level.validate_node(api, depth + 1, nodes, queries)?;
+                }
+                Ok(())
+            }
+            PermissionLevel::Not(level) => level.validate_node(api, depth + 1, nodes, queries),
+            PermissionLevel::Cw4Member { group_addr, .. } => {
+                api.addr_validate(group_addr)?;
+                count_query(queries)
+            }
+            PermissionLevel::Cw721Holder { collection_addr, token_id, min_count } => {
+                api.addr_validate(collection_addr)?;
+                if token_id.is_some() && min_count.is_some() {
+                    return Err(StdError::generic_err("Cw721Holder takes either a token_id or a min_count"));
+                }
+                if let Some(min_count) = min_count {
+                    if *min_count == 0 || *min_count > MAX_NFT_MIN_COUNT {
+                        return Err(StdError::generic_err(format!("Cw721Holder min_count must be between 1 and {}", MAX_NFT_MIN_COUNT)));
+                    }
+                }
+                count_query(queries)
+            }
+            PermissionLevel::MinBalance { token, .. } => {
+                token.validate(api)?;
+                count_query(queries)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn count_query(queries: &mut u32) -> StdResult<()> {
+    *queries += 1;
+    if *queries > MAX_PERMISSION_QUERIES {
+        return Err(StdError::generic_err(format!("Permission expression exceeds max of {} contract queries", MAX_PERMISSION_QUERIES)));
+    }
+    Ok(())
+}
+
+impl CollectionPermissions {
+    pub fn validate(&self, api: &dyn Api) -> StdResult<()> {
+        self.create.validate(api)?;
+        self.update.validate(api)?;
+        self.delete.validate(api)?;
+        self.read.validate(api)
+    }
 }
 
 impl Default for CollectionPermissions {
@@ -84,8 +253,469 @@ impl Default for CollectionPermissions {
     }
 }
 
+// Document lifecycle workflows
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WorkflowTransition {
+    pub from: String,
+    pub to: String,
+    pub permission: PermissionLevel,  // Who may move a document along this edge
+}
+
+// Overrides the collection read/update rules for documents in a given state
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StatePermissions {
+    pub state: String,
+    pub read: Option<PermissionLevel>,
+    pub update: Option<PermissionLevel>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollectionWorkflow {
+    pub states: Vec<String>,
+    pub initial_state: String,
+    pub transitions: Vec<WorkflowTransition>,
+    pub state_permissions: Vec<StatePermissions>,
+}
+
+impl CollectionWorkflow {
+    pub fn validate_permissions(&self, api: &dyn Api) -> StdResult<()> {
+        for transition in &self.transitions {
+            transition.permission.validate(api)?;
+        }
+        for overrides in &self.state_permissions {
+            if let Some(read) = &overrides.read {
+                read.validate(api)?;
+            }
+            if let Some(update) = &overrides.update {
+                update.validate(api)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn state_permissions(&self, state: &str) -> Option<&StatePermissions> {
+        self.state_permissions.iter().find(|p| p.state == state)
+    }
+}
+
+// Collection workflows: Map<collection_name, workflow>
+pub const COLLECTION_WORKFLOWS: Map<String, CollectionWorkflow> = Map::new("collection_workflows");
+
 // Collection-specific permissions: Map<collection_name, permissions>
 pub const COLLECTION_PERMISSIONS: Map<String, CollectionPermissions> = Map::new("collection_perms");
 
+// Field-level permissions, `path` is a dot separated JSON path like "profile.email"
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FieldRule {
+    pub path: String,
+    pub read: Option<PermissionLevel>,   // None leaves the field readable by anyone
+    pub write: Option<PermissionLevel>,  // None leaves the field to the document rules
+}
+
+// Field rules: Map<collection_name, rules>
+pub const COLLECTION_FIELD_RULES: Map<String, Vec<FieldRule>> = Map::new("collection_field_rules");
+
+// Compiled security rules: Map<collection_name, rules>
+pub const COLLECTION_RULES: Map<String, CollectionRules> = Map::new("collection_rules");
+
 // User roles system
 pub const USER_ROLES: Map<Addr, Vec<String>> = Map::new("user_roles");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleGrant {
+    pub granter: Addr,
+    pub granted_at: Timestamp,
+    pub expires_at: Option<Timestamp>,  // None never expires
+}
+
+// Grant details: Map<(user, role), grant>, kept in sync with USER_ROLES
+pub const ROLE_GRANTS: Map<(Addr, String), RoleGrant> = Map::new("role_grants");
+
+// Reverse index of USER_ROLES: Map<(role, user), Empty>
+pub const ROLE_MEMBERS: Map<(String, Addr), Empty> = Map::new("role_members");
+
+// Number of users holding each role: Map<role, count>
+pub const ROLE_MEMBER_COUNTS: Map<String, u64> = Map::new("role_member_counts");
+
+// Delegated role managers: Map<(role, manager), Empty>
+pub const ROLE_MANAGERS: Map<(String, Addr), Empty> = Map::new("role_managers");
+
+// Delegated collection owners who manage its permissions: Map<(collection, owner), Empty>
+pub const COLLECTION_OWNERS: Map<(String, Addr), Empty> = Map::new("collection_owners");
+
+// Role hierarchy: Map<role, roles it inherits>. Holding "moderator" that
+// inherits "editor" satisfies RequireRole("editor").
+pub const ROLE_INHERITS: Map<String, Vec<String>> = Map::new("role_inherits");
+
+// Upper bound on the roles reachable from a single role
+pub const MAX_INHERITED_ROLES: usize = 64;
+
+// Document-level access control
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum DocumentAction {
+    Read,
+    Update,
+    Delete,
+    Share,  // Manage the document ACL
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum Principal {
+    Address(String),
+    Role(String),
+}
+
+impl Principal {
+    // Key used by the SHARED_DOCUMENTS reverse index
+    pub fn index_key(&self) -> String {
+        match self {
+            Principal::Address(addr) => format!("addr:{}", addr),
+            Principal::Role(role) => format!("role:{}", role),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AclEntry {
+    pub principal: Principal,
+    pub actions: Vec<DocumentAction>,
+}
+
+// Per-document ACLs: Map<(collection, doc_id), entries>
+pub const DOCUMENT_ACLS: Map<DocumentKey, Vec<AclEntry>> = Map::new("document_acls");
+
+// Reverse index: Map<(principal_key, collection, doc_id), actions>
+pub const SHARED_DOCUMENTS: Map<(String, String, String), Vec<DocumentAction>> = Map::new("shared_documents");
+
+impl Document {
+    // Hex-encoded sha256 of the document data, used to pin a specific version
+    pub fn content_hash(&self) -> String {
+        Sha256::digest(self.data.as_bytes())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+}
+
+// Multi-party signing
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum SignatureDecision {
+    Sign,
+    Reject,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum SignatureStatus {
+    Pending,      // Waiting for signatures
+    Executed,     // Threshold of signatures reached
+    Rejected,     // Too many rejections to ever reach the threshold
+    Invalidated,  // Document changed after the request was created
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Signature {
+    pub signer: Addr,
+    pub decision: SignatureDecision,
+    pub signed_at: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SignatureRequest {
+    pub id: u64,
+    pub collection: String,
+    pub document: String,
+    pub content_hash: String,  // Hash of the document version being signed
+    pub requester: Addr,
+    pub signers: Vec<Addr>,
+    pub threshold: u32,
+    pub signatures: Vec<Signature>,
+    pub status: SignatureStatus,
+    pub created_at: Timestamp,
+    pub executed_at: Option<Timestamp>,
+}
+
+pub const SIGNATURE_REQUEST_COUNT: Item<u64> = Item::new("signature_request_count");
+
+pub struct SignatureRequestIndexes<'a> {
+    pub document: MultiIndex<'a, (String, String), SignatureRequest, u64>,
+}
+
+impl<'a> IndexList<SignatureRequest> for SignatureRequestIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<SignatureRequest>> + '_> {
+        let v: Vec<&dyn Index<SignatureRequest>> = vec![&self.document];
+        Box::new(v.into_iter())
+    }
+}
+
+// Signature requests: Map<request_id, request>, indexed by document
+pub const SIGNATURE_REQUESTS: IndexedMap<u64, SignatureRequest, SignatureRequestIndexes> = IndexedMap::new(
+    "signature_requests",
+    SignatureRequestIndexes {
+        document: MultiIndex::new(
+            |_pk: &[u8], r: &SignatureRequest| (r.collection.clone(), r.document.clone()),
+            "signature_requests",
+            "signature_requests__document"
+        ),
+    },
+);
+
+// Pending signature requests per document, invalidated on any write
+pub const DOCUMENT_SIGNATURE_REQUESTS: Map<DocumentKey, Vec<u64>> = Map::new("document_signature_requests");
+
+// Write pricing, all amounts in one token. Fees are kept by the contract,
+// deposits are held per document and refunded on delete.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollectionPricing {
+    pub token: TokenDenom,
+    pub write_fee: Uint128,  // Charged on every set and update
+    pub byte_fee: Uint128,   // Charged per byte of the written data
+    pub deposit: Uint128,    // Charged when a document is created
+}
+
+// Pricing: Map<collection_name, pricing>
+pub const COLLECTION_PRICING: Map<String, CollectionPricing> = Map::new("collection_pricing");
+
+// cw20 contracts the admin accepts as pricing tokens: Map<contract, Empty>.
+// A token contract vouches for the sender of its Receive, so only trusted
+// ones may be used.
+pub const ACCEPTED_CW20: Map<Addr, Empty> = Map::new("accepted_cw20");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Deposit {
+    pub depositor: Addr,
+    pub token: TokenDenom,
+    pub amount: Uint128,
+}
+
+// Refundable deposits: Map<(collection, document), deposit>
+pub const DOCUMENT_DEPOSITS: Map<DocumentKey, Deposit> = Map::new("document_deposits");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeBalance {
+    pub token: TokenDenom,
+    pub amount: Uint128,
+}
+
+// Fees available for withdrawal: Map<token_key, balance>
+pub const COLLECTED_FEES: Map<String, FeeBalance> = Map::new("collected_fees");
+
+// Storage limits per owner. None fields are unlimited
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct Quota {
+    pub max_documents: Option<u64>,      // Documents owned
+    pub max_bytes: Option<u64>,          // Total bytes of data owned
+    pub max_document_size: Option<u64>,  // Bytes of data in a single document
+}
+
+// Quotas: Map<collection_name, quota>, plus one applied across all collections
+pub const COLLECTION_QUOTAS: Map<String, Quota> = Map::new("collection_quotas");
+pub const GLOBAL_QUOTA: Item<Quota> = Item::new("global_quota");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct Usage {
+    pub documents: u64,
+    pub bytes: u64,
+}
+
+// Running usage counters: Map<owner, usage> and Map<(owner, collection), usage>
+pub const USER_USAGE: Map<Addr, Usage> = Map::new("user_usage");
+pub const COLLECTION_USAGE: Map<(Addr, String), Usage> = Map::new("collection_usage");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum RateWindow {
+    Blocks(u64),
+    Seconds(u64),
+}
+
+impl RateWindow {
+    pub fn length(&self) -> u64 {
+        match self {
+            RateWindow::Blocks(blocks) => *blocks,
+            RateWindow::Seconds(seconds) => *seconds,
+        }
+    }
+
+    // Current position in the window's unit
+    pub fn now(&self, env: &Env) -> u64 {
+        match self {
+            RateWindow::Blocks(_) => env.block.height,
+            RateWindow::Seconds(_) => env.block.time.seconds(),
+        }
+    }
+
+    pub fn unit(&self) -> &'static str {
+        match self {
+            RateWindow::Blocks(_) => "height",
+            RateWindow::Seconds(_) => "time",
+        }
+    }
+}
+
+// Token bucket: up to max_writes per window per sender, refilling evenly
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RateLimit {
+    pub max_writes: u32,
+    pub window: RateWindow,
+    pub exempt_roles: Vec<String>,  // Holders of these roles are not limited
+}
+
+// Rate limits: Map<collection_name, limit>
+pub const COLLECTION_RATE_LIMITS: Map<String, RateLimit> = Map::new("collection_rate_limits");
+
+// Time at which a sender's bucket is full again, in window units multiplied
+// by max_writes so refills stay integral. Only valid for the limit it was
+// measured under, a changed limit starts from a full bucket.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RateBucket {
+    pub full_at: Uint128,
+    pub max_writes: u32,
+    pub window: RateWindow,
+}
+
+// Per sender buckets: Map<(collection, sender), bucket>
+pub const RATE_LIMIT_BUCKETS: Map<(String, Addr), RateBucket> = Map::new("rate_limit_buckets");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum WriteKind {
+    Set,
+    Update,  // Includes workflow transitions
+    Delete,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum PauseScope {
+    Contract,            // Every document write
+    Collection(String),  // Writes to one collection
+    Write(WriteKind),    // One kind of write in every collection
+}
+
+impl PauseScope {
+    pub fn key(&self) -> String {
+        match self {
+            PauseScope::Contract => "contract".to_string(),
+            PauseScope::Collection(collection) => format!("collection:{}", collection),
+            PauseScope::Write(kind) => format!("write:{:?}", kind),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PauseInfo {
+    pub scope: PauseScope,
+    pub reason: Option<String>,
+    pub paused_by: Addr,
+    pub paused_at: Timestamp,
+}
+
+// Active pauses: Map<scope_key, pause>
+pub const PAUSES: Map<String, PauseInfo> = Map::new("pauses");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum CollectionOpKind {
+    Drop,
+    Rename { to: String },
+    Copy { to: String },
+}
+
+// Collection operation running in chunks. The source and target collections
+// are locked for writes until it finishes.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollectionOp {
+    pub kind: CollectionOpKind,
+    pub started_by: Addr,
+    pub started_at: Timestamp,
+    pub cursor: Option<String>,  // Last document processed
+    pub processed: u64,
+}
+
+// Running operations: Map<source_collection, op>
+pub const COLLECTION_OPS: Map<String, CollectionOp> = Map::new("collection_ops");
+
+// Collections locked by a running operation: Map<collection, source_collection>
+pub const COLLECTION_LOCKS: Map<String, String> = Map::new("collection_locks");
+
+// Running totals of an import, compared against the export by FinishImport
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct ImportProgress {
+    pub records: u64,
+    pub checksum: String,
+}
+
+pub const IMPORT_PROGRESS: Item<ImportProgress> = Item::new("import_progress");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum ChangeOp {
+    Set,
+    Update,
+    Delete,
+    Transition,
+}
+
+impl ChangeOp {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeOp::Set => "set",
+            ChangeOp::Update => "update",
+            ChangeOp::Delete => "delete",
+            ChangeOp::Transition => "transition",
+        }
+    }
+}
+
+// One entry of the change feed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Change {
+    pub sequence: u64,
+    pub collection: String,
+    pub document: String,
+    pub op: ChangeOp,
+    pub height: u64,
+    pub time: Timestamp,
+    pub content_hash: Option<String>,  // None for deletes
+}
+
+pub struct ChangeIndexes<'a> {
+    pub collection: MultiIndex<'a, String, Change, u64>,
+}
+
+impl<'a> IndexList<Change> for ChangeIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Change>> + '_> {
+        let v: Vec<&dyn Index<Change>> = vec![&self.collection];
+        Box::new(v.into_iter())
+    }
+}
+
+// Append-only change feed: Map<sequence, change>
+pub const CHANGES: IndexedMap<u64, Change, ChangeIndexes> = IndexedMap::new(
+    "changes",
+    ChangeIndexes {
+        collection: MultiIndex::new(
+            |_pk: &[u8], c: &Change| c.collection.clone(),
+            "changes",
+            "changes__collection"
+        ),
+    },
+);
+
+// Last sequence number handed out, the feed starts at 1
+pub const CHANGE_SEQUENCE: Item<u64> = Item::new("change_sequence");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum HookMode {
+    BestEffort,  // Failures are ignored, the write stands
+    Required,    // Failures revert the write
+}
+
+// Contract notified after every document write to a collection. Collection
+// drops, renames and copies and imports are bulk admin operations and skip hooks.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Hook {
+    pub contract: Addr,
+    pub mode: HookMode,
+}
+
+// Each hook costs a submessage per write
+pub const MAX_COLLECTION_HOOKS: usize = 5;
+
+// Write hooks: Map<collection_name, hooks>
+pub const COLLECTION_HOOKS: Map<String, Vec<Hook>> = Map::new("collection_hooks");
@@ -0,0 +1,93 @@
+mod common;
+
+use docustore::msg::{ExecuteMsg, QueryMsg, SharedDocumentsResponse};
+use docustore::state::{
+    AclEntry, CollectionWorkflow, DocumentAction, PermissionLevel, Principal, StatePermissions,
+};
+
+use common::{error_text, owner_only, Suite, ADMIN, ALICE, BOB, CAROL};
+
+fn setup() -> Suite {
+    let mut suite = Suite::new();
+    suite.set_permissions("notes", owner_only());
+    suite.set(ALICE, "notes", "n1", r#"{"text":"hi"}"#).unwrap();
+    suite
+}
+
+fn share(suite: &mut Suite, sender: &str, entries: Vec<AclEntry>) -> anyhow::Result<()> {
+    suite.execute(sender, &ExecuteMsg::SetDocumentAcl {
+        collection: "notes".to_string(),
+        document: "n1".to_string(),
+        entries,
+    })
+    .map(|_| ())
+}
+
+fn entry(principal: Principal, actions: Vec<DocumentAction>) -> AclEntry {
+    AclEntry { principal, actions }
+}
+
+#[test]
+fn address_entries_grant_only_the_listed_actions() {
+    let mut suite = setup();
+    assert!(suite.update(BOB, "notes", "n1", r#"{"text":"bob"}"#).is_err());
+
+    share(&mut suite, ALICE, vec![entry(Principal::Address(BOB.to_string()), vec![DocumentAction::Update])]).unwrap();
+    suite.update(BOB, "notes", "n1", r#"{"text":"bob"}"#).unwrap();
+    assert!(suite.delete(BOB, "notes", "n1").is_err());
+    assert!(!suite.can("notes", "n1", CAROL, "update"));
+
+    let shared: SharedDocumentsResponse = suite.query(&QueryMsg::ListSharedDocuments {
+        user: BOB.to_string(),
+        limit: None,
+        start_after: None,
+    });
+    assert_eq!(shared.documents.len(), 1);
+    assert_eq!(shared.documents[0].actions, vec![DocumentAction::Update]);
+}
+
+#[test]
+fn role_entries_grant_every_holder_of_the_role() {
+    let mut suite = setup();
+    share(&mut suite, ALICE, vec![entry(Principal::Role("moderator".to_string()), vec![DocumentAction::Delete])]).unwrap();
+    assert!(!suite.can("notes", "n1", CAROL, "delete"));
+
+    suite.grant_role(CAROL, "moderator");
+    suite.delete(CAROL, "notes", "n1").unwrap();
+}
+
+#[test]
+fn only_owners_and_sharers_manage_the_acl() {
+    let mut suite = setup();
+    let bob_reads = vec![entry(Principal::Address(BOB.to_string()), vec![DocumentAction::Read])];
+    let err = share(&mut suite, BOB, bob_reads.clone()).unwrap_err();
+    assert!(error_text(err).contains("share permission"));
+
+    share(&mut suite, ALICE, vec![entry(Principal::Address(BOB.to_string()), vec![DocumentAction::Share])]).unwrap();
+    share(&mut suite, BOB, bob_reads).unwrap();
+    // Bob replaced the ACL and with it his own share grant
+    assert!(!suite.can("notes", "n1", BOB, "share"));
+}
+
+#[test]
+fn state_overrides_take_precedence_over_acl_grants() {
+    let mut suite = setup();
+    suite.execute(ADMIN, &ExecuteMsg::SetCollectionWorkflow {
+        collection: "notes".to_string(),
+        workflow: Some(CollectionWorkflow {
+            states: vec!["open".to_string(), "locked".to_string()],
+            initial_state: "locked".to_string(),
+            transitions: vec![],
+            state_permissions: vec![StatePermissions {
+                state: "locked".to_string(),
+                read: None,
+                update: Some(PermissionLevel::AdminOnly),
+            }],
+        }),
+    })
+    .unwrap();
+    share(&mut suite, ALICE, vec![entry(Principal::Address(BOB.to_string()), vec![DocumentAction::Update])]).unwrap();
+
+    assert!(!suite.can("notes", "n1", BOB, "update"));
+    assert!(suite.update(BOB, "notes", "n1", r#"{"text":"bob"}"#).is_err());
+}
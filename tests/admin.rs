@@ -0,0 +1,172 @@
+mod common;
+
+use cosmwasm_std::Timestamp;
+use cw_multi_test::AppResponse;
+use docustore::msg::{AdminResponse, ExecuteMsg, QueryMsg};
+use docustore::state::{AdminAction, AdminActionStatus};
+
+use common::{error_text, Suite, ADMIN, ALICE, BOB, CAROL};
+
+fn admin_set(threshold: u32) -> Suite {
+    let mut suite = Suite::new();
+    suite.execute(ADMIN, &ExecuteMsg::SetAdminSet {
+        members: vec![ALICE.to_string(), BOB.to_string(), CAROL.to_string()],
+        threshold,
+    })
+    .unwrap();
+    suite
+}
+
+fn grant_editor() -> ExecuteMsg {
+    ExecuteMsg::GrantRole {
+        user: "dave".to_string(),
+        role: "editor".to_string(),
+        expires_at: None,
+    }
+}
+
+fn propose(suite: &mut Suite, sender: &str, msg: ExecuteMsg) -> anyhow::Result<AppResponse> {
+    suite.execute(sender, &ExecuteMsg::ProposeAdminAction { msg: Box::new(msg), expires_at: None })
+}
+
+fn action(suite: &Suite, action_id: u64) -> AdminAction {
+    suite.query(&QueryMsg::GetAdminAction { action_id })
+}
+
+fn dave_roles(suite: &Suite) -> Vec<String> {
+    suite.query(&QueryMsg::GetUserRoles { user: "dave".to_string() })
+}
+
+// The op of every docustore_admin event, in emission order
+fn admin_ops(response: &AppResponse) -> Vec<String> {
+    response.events.iter()
+        .filter(|event| event.ty == "wasm-docustore_admin")
+        .flat_map(|event| event.attributes.iter().filter(|attr| attr.key == "op"))
+        .map(|attr| attr.value.clone())
+        .collect()
+}
+
+#[test]
+fn the_admin_set_replaces_the_single_admin() {
+    let mut suite = admin_set(2);
+    let admin: AdminResponse = suite.query(&QueryMsg::GetAdmin {});
+    assert_eq!(admin.admin, Some(suite.contract.clone()));
+    assert!(suite.execute(ADMIN, &grant_editor()).is_err());
+    assert!(suite.execute(ALICE, &grant_editor()).is_err());
+}
+
+#[test]
+fn actions_run_once_the_threshold_approves() {
+    let mut suite = admin_set(2);
+    let response = propose(&mut suite, ALICE, grant_editor()).unwrap();
+    assert_eq!(admin_ops(&response), vec!["propose_action"]);
+    assert_eq!(action(&suite, 1).status, AdminActionStatus::Pending);
+    assert!(dave_roles(&suite).is_empty());
+
+    let err = suite.execute(ALICE, &ExecuteMsg::ApproveAdminAction { action_id: 1 }).unwrap_err();
+    assert!(error_text(err).contains("already approved"));
+
+    let response = suite.execute(BOB, &ExecuteMsg::ApproveAdminAction { action_id: 1 }).unwrap();
+    assert_eq!(admin_ops(&response), vec!["execute_action", "approve_action"]);
+    assert_eq!(action(&suite, 1).status, AdminActionStatus::Executed);
+    assert_eq!(dave_roles(&suite), vec!["editor"]);
+}
+
+#[test]
+fn only_members_propose_and_only_privileged_messages() {
+    let mut suite = admin_set(2);
+    let err = propose(&mut suite, "dave", grant_editor()).unwrap_err();
+    assert!(error_text(err).contains("Only admin set members"));
+
+    let write = ExecuteMsg::Set {
+        collection: "posts".to_string(),
+        document: "p1".to_string(),
+        data: "{}".to_string(),
+    };
+    let err = propose(&mut suite, ALICE, write).unwrap_err();
+    assert!(error_text(err).contains("not a privileged action"));
+}
+
+#[test]
+fn proposers_can_cancel_pending_actions() {
+    let mut suite = admin_set(2);
+    propose(&mut suite, ALICE, grant_editor()).unwrap();
+    assert!(suite.execute(BOB, &ExecuteMsg::CancelAdminAction { action_id: 1 }).is_err());
+    suite.execute(ALICE, &ExecuteMsg::CancelAdminAction { action_id: 1 }).unwrap();
+
+    assert_eq!(action(&suite, 1).status, AdminActionStatus::Cancelled);
+    assert!(suite.execute(BOB, &ExecuteMsg::ApproveAdminAction { action_id: 1 }).is_err());
+    assert!(dave_roles(&suite).is_empty());
+}
+
+#[test]
+fn a_failing_action_reverts_its_approval() {
+    let mut suite = admin_set(2);
+    let invalid = ExecuteMsg::SetAdminSet { members: vec![ALICE.to_string()], threshold: 2 };
+    propose(&mut suite, ALICE, invalid).unwrap();
+
+    assert!(suite.execute(BOB, &ExecuteMsg::ApproveAdminAction { action_id: 1 }).is_err());
+    let pending = action(&suite, 1);
+    assert_eq!(pending.status, AdminActionStatus::Pending);
+    assert_eq!(pending.approvals.len(), 1);
+}
+
+fn propose_admin(suite: &mut Suite, new_admin: &str, expires_at: Option<Timestamp>) {
+    suite.execute(ADMIN, &ExecuteMsg::ProposeAdmin { new_admin: new_admin.to_string(), expires_at }).unwrap();
+}
+
+fn current_admin(suite: &Suite) -> AdminResponse {
+    suite.query(&QueryMsg::GetAdmin {})
+}
+
+#[test]
+fn the_proposed_admin_takes_over_on_accept() {
+    let mut suite = Suite::new();
+    let err = suite.execute(ALICE, &ExecuteMsg::ProposeAdmin { new_admin: BOB.to_string(), expires_at: None }).unwrap_err();
+    assert!(error_text(err).contains("Only admin"));
+
+    propose_admin(&mut suite, ALICE, None);
+    let pending = current_admin(&suite).pending.unwrap();
+    assert_eq!(pending.new_admin.as_str(), ALICE);
+    let err = suite.execute(BOB, &ExecuteMsg::AcceptAdmin {}).unwrap_err();
+    assert!(error_text(err).contains("Only the proposed admin"));
+
+    suite.execute(ALICE, &ExecuteMsg::AcceptAdmin {}).unwrap();
+    let response = current_admin(&suite);
+    assert_eq!(response.admin.unwrap().as_str(), ALICE);
+    assert!(response.pending.is_none());
+    assert!(suite.execute(ADMIN, &grant_editor()).is_err());
+    suite.execute(ALICE, &grant_editor()).unwrap();
+}
+
+#[test]
+fn proposals_expire_and_can_be_cancelled() {
+    let mut suite = Suite::new();
+    let expires_at = suite.app.block_info().time.plus_seconds(60);
+    propose_admin(&mut suite, ALICE, Some(expires_at));
+    suite.advance_seconds(60);
+    let err = suite.execute(ALICE, &ExecuteMsg::AcceptAdmin {}).unwrap_err();
+    assert!(error_text(err).contains("expired"));
+
+    // A new proposal replaces the expired one
+    propose_admin(&mut suite, BOB, None);
+    suite.execute(ADMIN, &ExecuteMsg::CancelAdminProposal {}).unwrap();
+    assert!(current_admin(&suite).pending.is_none());
+    let err = suite.execute(BOB, &ExecuteMsg::AcceptAdmin {}).unwrap_err();
+    assert!(error_text(err).contains("No pending admin proposal"));
+    assert_eq!(current_admin(&suite).admin.unwrap().as_str(), ADMIN);
+}
+
+#[test]
+fn renouncing_leaves_no_admin() {
+    let mut suite = Suite::new();
+    propose_admin(&mut suite, ALICE, None);
+    suite.execute(ADMIN, &ExecuteMsg::RenounceAdmin {}).unwrap();
+
+    let response = current_admin(&suite);
+    assert!(response.admin.is_none());
+    assert!(response.pending.is_none());
+    assert!(suite.execute(ALICE, &ExecuteMsg::AcceptAdmin {}).is_err());
+    let err = suite.execute(ADMIN, &grant_editor()).unwrap_err();
+    assert!(error_text(err).contains("Only admin"));
+}
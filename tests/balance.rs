@@ -0,0 +1,122 @@
+mod common;
+
+use cosmwasm_std::{
+    coins, to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+    StdError, StdResult, Uint128,
+};
+use cw20::{BalanceResponse, Cw20QueryMsg};
+use cw_multi_test::{Contract, ContractWrapper, Executor};
+use cw_storage_plus::Map;
+use serde::{Deserialize, Serialize};
+
+use docustore::msg::QueryMsg;
+use docustore::state::{CollectionPermissions, PermissionLevel, TokenDenom};
+
+use common::{Suite, ADMIN, ALICE, BOB};
+
+const DENOM: &str = "uxion";
+
+// Minimal cw20 stand-in: balances by address and the Balance query the
+// permission check makes
+const BALANCES: Map<&str, Uint128> = Map::new("balances");
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct BalancesMsg {
+    balances: Vec<(String, Uint128)>,
+}
+
+fn token_instantiate(deps: DepsMut, env: Env, info: MessageInfo, msg: BalancesMsg) -> StdResult<Response> {
+    token_execute(deps, env, info, msg)
+}
+
+fn token_execute(deps: DepsMut, _env: Env, _info: MessageInfo, msg: BalancesMsg) -> StdResult<Response> {
+    for (addr, balance) in msg.balances {
+        BALANCES.save(deps.storage, &addr, &balance)?;
+    }
+    Ok(Response::new())
+}
+
+fn token_query(deps: Deps, _env: Env, msg: Cw20QueryMsg) -> StdResult<Binary> {
+    match msg {
+        Cw20QueryMsg::Balance { address } => to_json_binary(&BalanceResponse {
+            balance: BALANCES.may_load(deps.storage, &address)?.unwrap_or_default(),
+        }),
+        _ => Err(StdError::generic_err("Unsupported")),
+    }
+}
+
+fn token_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(token_execute, token_instantiate, token_query))
+}
+
+// Alice holds 100 uxion and 100 cw20 tokens, Bob holds neither
+fn setup() -> (Suite, Addr) {
+    let mut suite = Suite::with_balances(vec![(ALICE, coins(100, DENOM))]);
+    let code_id = suite.app.store_code(token_contract());
+    let token = suite.app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(ADMIN),
+            &BalancesMsg { balances: vec![(ALICE.to_string(), Uint128::new(100))] },
+            &[],
+            "token",
+            None,
+        )
+        .unwrap();
+    (suite, token)
+}
+
+fn gate(suite: &mut Suite, collection: &str, token: TokenDenom, amount: u128) {
+    suite.set_permissions(collection, CollectionPermissions {
+        create: PermissionLevel::MinBalance { token, amount: Uint128::new(amount) },
+        update: PermissionLevel::OwnerOnly,
+        delete: PermissionLevel::OwnerOnly,
+        read: PermissionLevel::Anyone,
+    });
+}
+
+fn can_create(suite: &Suite, collection: &str, user: &str) -> bool {
+    suite.query(&QueryMsg::CheckPermission {
+        collection: collection.to_string(),
+        user: user.to_string(),
+        action: "create".to_string(),
+    })
+}
+
+#[test]
+fn native_balances_are_checked_at_write_time() {
+    let (mut suite, _) = setup();
+    gate(&mut suite, "whales", TokenDenom::Native(DENOM.to_string()), 100);
+    assert!(can_create(&suite, "whales", ALICE));
+    assert!(!can_create(&suite, "whales", BOB));
+
+    let send: CosmosMsg = BankMsg::Send { to_address: BOB.to_string(), amount: coins(1, DENOM) }.into();
+    suite.app.execute(Addr::unchecked(ALICE), send).unwrap();
+    assert!(suite.set(ALICE, "whales", "a1", "{}").is_err());
+    assert!(!can_create(&suite, "whales", BOB));
+}
+
+#[test]
+fn cw20_balances_are_queried_from_the_token() {
+    let (mut suite, token) = setup();
+    gate(&mut suite, "holders", TokenDenom::Cw20(token.to_string()), 50);
+    suite.set(ALICE, "holders", "a1", "{}").unwrap();
+    assert!(suite.set(BOB, "holders", "b1", "{}").is_err());
+
+    suite.app
+        .execute_contract(
+            Addr::unchecked(ADMIN),
+            token.clone(),
+            &BalancesMsg { balances: vec![(BOB.to_string(), Uint128::new(50))] },
+            &[],
+        )
+        .unwrap();
+    suite.set(BOB, "holders", "b1", "{}").unwrap();
+}
+
+#[test]
+fn a_zero_amount_lets_everyone_through() {
+    let (mut suite, token) = setup();
+    gate(&mut suite, "open", TokenDenom::Cw20(token.to_string()), 0);
+    assert!(can_create(&suite, "open", BOB));
+}
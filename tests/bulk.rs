@@ -0,0 +1,128 @@
+mod common;
+
+use cosmwasm_std::from_json;
+use cw_multi_test::AppResponse;
+use docustore::msg::{BulkWriteResponse, ExecuteMsg};
+use docustore::state::{FieldRule, PermissionLevel, RateLimit, RateWindow};
+
+use common::{error_text, owner_only, Suite, ADMIN, ALICE, BOB};
+
+// Alice owns a1..a3 tagged "x" and a4 tagged "y", Bob owns b1 tagged "x"
+fn setup() -> Suite {
+    let mut suite = Suite::new();
+    suite.set_permissions("items", owner_only());
+    for id in ["a1", "a2", "a3"] {
+        suite.set(ALICE, "items", id, r#"{"tag":"x","meta":{"n":1}}"#).unwrap();
+    }
+    suite.set(ALICE, "items", "a4", r#"{"tag":"y","meta":{"n":1}}"#).unwrap();
+    suite.set(BOB, "items", "b1", r#"{"tag":"x","meta":{"n":1}}"#).unwrap();
+    suite
+}
+
+fn summary(response: &AppResponse) -> BulkWriteResponse {
+    from_json(response.data.as_ref().unwrap()).unwrap()
+}
+
+fn has_attribute(response: &AppResponse, key: &str) -> bool {
+    response.events.iter()
+        .filter(|event| event.ty == "wasm")
+        .any(|event| event.attributes.iter().any(|attr| attr.key == key))
+}
+
+fn update_where(suite: &mut Suite, filter: &str, limit: Option<u32>, start_after: Option<String>) -> AppResponse {
+    suite.execute(ALICE, &ExecuteMsg::UpdateWhere {
+        collection: "items".to_string(),
+        filter: filter.to_string(),
+        merge: r#"{"done":true}"#.to_string(),
+        limit,
+        start_after,
+    })
+    .unwrap()
+}
+
+#[test]
+fn delete_where_skips_documents_the_sender_cannot_delete() {
+    let mut suite = setup();
+    let response = suite.execute(ALICE, &ExecuteMsg::DeleteWhere {
+        collection: "items".to_string(),
+        filter: r#"{"tag":"x"}"#.to_string(),
+        limit: None,
+        start_after: None,
+    })
+    .unwrap();
+
+    assert_eq!(summary(&response), BulkWriteResponse { scanned: 5, matched: 4, processed: 3, next_start_after: None });
+    assert!(!has_attribute(&response, "next_start_after"));
+    assert!(suite.get("items", "a1", None).is_none());
+    assert!(suite.get("items", "a4", None).is_some());
+    assert!(suite.get("items", "b1", None).is_some());
+}
+
+#[test]
+fn update_where_pages_through_the_collection() {
+    let mut suite = setup();
+    let first = update_where(&mut suite, r#"{"meta.n":1}"#, Some(2), None);
+    let page = summary(&first);
+    assert_eq!(page.processed, 2);
+    assert_eq!(page.next_start_after.as_deref(), Some("a2"));
+    assert!(has_attribute(&first, "next_start_after"));
+
+    let second = update_where(&mut suite, r#"{"meta.n":1}"#, Some(10), page.next_start_after);
+    let page = summary(&second);
+    assert_eq!((page.scanned, page.processed, page.next_start_after), (3, 2, None));
+
+    let doc = suite.get("items", "a4", None).unwrap();
+    assert_eq!(doc.data, r#"{"done":true,"meta":{"n":1},"tag":"y"}"#);
+    assert!(!suite.get("items", "b1", None).unwrap().data.contains("done"));
+}
+
+#[test]
+fn invalid_filters_are_rejected() {
+    let mut suite = setup();
+    let result = suite.execute(ALICE, &ExecuteMsg::DeleteWhere {
+        collection: "items".to_string(),
+        filter: r#"["tag"]"#.to_string(),
+        limit: None,
+        start_after: None,
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn a_bulk_call_counts_once_against_the_rate_limit() {
+    let mut suite = setup();
+    suite.execute(ADMIN, &ExecuteMsg::SetCollectionRateLimit {
+        collection: "items".to_string(),
+        limit: Some(RateLimit { max_writes: 2, window: RateWindow::Blocks(10), exempt_roles: vec![] }),
+    })
+    .unwrap();
+
+    let page = summary(&update_where(&mut suite, r#"{"meta.n":1}"#, None, None));
+    assert_eq!(page.processed, 4);
+    update_where(&mut suite, r#"{"meta.n":1}"#, None, None);
+    let err = suite.execute(ALICE, &ExecuteMsg::UpdateWhere {
+        collection: "items".to_string(),
+        filter: r#"{"meta.n":1}"#.to_string(),
+        merge: r#"{"done":false}"#.to_string(),
+        limit: None,
+        start_after: None,
+    })
+    .unwrap_err();
+    assert!(error_text(err).starts_with("Rate limited"));
+}
+
+#[test]
+fn documents_with_protected_fields_are_skipped() {
+    let mut suite = setup();
+    suite.update(ALICE, "items", "a2", r#"{"done":true}"#).unwrap();
+    suite.execute(ADMIN, &ExecuteMsg::SetFieldRules {
+        collection: "items".to_string(),
+        rules: vec![FieldRule { path: "done".to_string(), read: None, write: Some(PermissionLevel::AdminOnly) }],
+    })
+    .unwrap();
+
+    // a2 already has the value, a1 and a3 would gain the protected field
+    let page = summary(&update_where(&mut suite, r#"{"tag":"x"}"#, None, None));
+    assert_eq!((page.matched, page.processed), (4, 1));
+    assert!(!suite.get("items", "a1", None).unwrap().data.contains("done"));
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use docustore::msg::{ChangesResponse, QueryMsg};
+use docustore::state::ChangeOp;
+
+use common::{Suite, ALICE};
+
+fn changes(suite: &Suite, sequence: u64, collection: Option<&str>, limit: Option<u32>) -> ChangesResponse {
+    suite.query(&QueryMsg::ChangesSince {
+        sequence,
+        collection: collection.map(str::to_string),
+        limit,
+    })
+}
+
+#[test]
+fn every_write_gets_the_next_sequence() {
+    let mut suite = Suite::new();
+    suite.set(ALICE, "notes", "n1", r#"{"a":1}"#).unwrap();
+    suite.update(ALICE, "notes", "n1", r#"{"b":2}"#).unwrap();
+    suite.delete(ALICE, "notes", "n1").unwrap();
+
+    let feed = changes(&suite, 0, None, None);
+    let ops: Vec<(u64, ChangeOp)> = feed.changes.iter().map(|c| (c.sequence, c.op.clone())).collect();
+    assert_eq!(ops, vec![(1, ChangeOp::Set), (2, ChangeOp::Update), (3, ChangeOp::Delete)]);
+    assert_eq!((feed.next_sequence, feed.latest_sequence), (3, 3));
+
+    assert!(feed.changes[0].content_hash.is_some());
+    assert_ne!(feed.changes[0].content_hash, feed.changes[1].content_hash);
+    assert!(feed.changes[2].content_hash.is_none());
+}
+
+#[test]
+fn the_feed_pages_and_filters_by_collection() {
+    let mut suite = Suite::new();
+    for i in 0..3 {
+        suite.set(ALICE, "notes", &format!("n{}", i), "{}").unwrap();
+        suite.set(ALICE, "tasks", &format!("t{}", i), "{}").unwrap();
+    }
+
+    let first = changes(&suite, 0, Some("tasks"), Some(2));
+    let sequences: Vec<u64> = first.changes.iter().map(|c| c.sequence).collect();
+    assert_eq!(sequences, vec![2, 4]);
+    assert_eq!((first.next_sequence, first.latest_sequence), (4, 6));
+
+    let second = changes(&suite, first.next_sequence, Some("tasks"), Some(2));
+    assert_eq!(second.changes.len(), 1);
+    assert_eq!(second.changes[0].document, "t2");
+    assert_eq!(second.next_sequence, second.latest_sequence);
+
+    // A caught up client keeps its sequence
+    let empty = changes(&suite, 6, None, None);
+    assert!(empty.changes.is_empty());
+    assert_eq!(empty.next_sequence, 6);
+}
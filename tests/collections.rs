@@ -0,0 +1,177 @@
+mod common;
+
+use cosmwasm_std::{coins, Addr, Uint128};
+use docustore::msg::{ExecuteMsg, QueryMsg};
+use docustore::state::{
+    CollectionOp, CollectionOpKind, CollectionPermissions, CollectionPricing, Deposit, PauseScope, TokenDenom,
+};
+
+use common::{error_text, owner_only, Suite, ADMIN, ALICE, BOB};
+
+const DENOM: &str = "uxion";
+
+// Alice owns n1..n5 in "notes"
+fn setup() -> Suite {
+    let mut suite = Suite::new();
+    for i in 1..=5 {
+        suite.set(ALICE, "notes", &format!("n{}", i), "{}").unwrap();
+    }
+    suite
+}
+
+fn drop_msg(limit: Option<u32>) -> ExecuteMsg {
+    ExecuteMsg::DropCollection { collection: "notes".to_string(), limit }
+}
+
+fn rename_msg(limit: Option<u32>) -> ExecuteMsg {
+    ExecuteMsg::RenameCollection { from: "notes".to_string(), to: "archive".to_string(), limit }
+}
+
+fn copy_msg(limit: Option<u32>) -> ExecuteMsg {
+    ExecuteMsg::CopyCollection { from: "notes".to_string(), to: "archive".to_string(), limit }
+}
+
+// Which of n1..n5 exist in the collection
+fn document_ids(suite: &Suite, collection: &str) -> Vec<String> {
+    (1..=5)
+        .map(|i| format!("n{}", i))
+        .filter(|id| suite.get(collection, id, None).is_some())
+        .collect()
+}
+
+fn running_op(suite: &Suite, collection: &str) -> Option<CollectionOp> {
+    suite.query(&QueryMsg::GetCollectionOp { collection: collection.to_string() })
+}
+
+#[test]
+fn drop_removes_documents_and_settings() {
+    let mut suite = setup();
+    suite.set_permissions("notes", owner_only());
+    let err = suite.execute(ALICE, &drop_msg(None)).unwrap_err();
+    assert!(error_text(err).contains("Only admin"));
+
+    suite.execute(ADMIN, &drop_msg(None)).unwrap();
+    assert!(document_ids(&suite, "notes").is_empty());
+    let permissions: CollectionPermissions =
+        suite.query(&QueryMsg::GetCollectionPermissions { collection: "notes".to_string() });
+    assert_eq!(permissions, CollectionPermissions::default());
+    assert!(running_op(&suite, "notes").is_none());
+}
+
+#[test]
+fn rename_moves_documents_and_copy_keeps_the_source() {
+    let mut suite = setup();
+    suite.execute(ADMIN, &copy_msg(None)).unwrap();
+    assert_eq!(document_ids(&suite, "notes").len(), 5);
+    assert_eq!(document_ids(&suite, "archive"), document_ids(&suite, "notes"));
+
+    let err = suite.execute(ADMIN, &rename_msg(None)).unwrap_err();
+    assert!(error_text(err).contains("already has documents"));
+    suite.execute(ADMIN, &ExecuteMsg::DropCollection { collection: "archive".to_string(), limit: None }).unwrap();
+
+    suite.execute(ADMIN, &rename_msg(None)).unwrap();
+    assert!(document_ids(&suite, "notes").is_empty());
+    assert_eq!(document_ids(&suite, "archive").len(), 5);
+    let doc = suite.get("archive", "n1", None).unwrap();
+    assert_eq!(doc.owner, Addr::unchecked(ALICE));
+}
+
+#[test]
+fn chunks_resume_from_the_cursor_and_anyone_can_continue() {
+    let mut suite = setup();
+    suite.execute(ADMIN, &rename_msg(Some(2))).unwrap();
+    let op = running_op(&suite, "notes").unwrap();
+    assert_eq!(op.kind, CollectionOpKind::Rename { to: "archive".to_string() });
+    assert_eq!(op.cursor.as_deref(), Some("n2"));
+    assert_eq!(op.processed, 2);
+    assert_eq!(document_ids(&suite, "archive"), vec!["n1", "n2"]);
+
+    let err = suite.execute(ADMIN, &drop_msg(Some(2))).unwrap_err();
+    assert!(error_text(err).contains("different operation"));
+
+    suite.execute(BOB, &rename_msg(Some(2))).unwrap();
+    assert_eq!(running_op(&suite, "notes").unwrap().cursor.as_deref(), Some("n4"));
+    suite.execute(BOB, &rename_msg(Some(2))).unwrap();
+    assert!(running_op(&suite, "notes").is_none());
+    assert_eq!(document_ids(&suite, "archive").len(), 5);
+}
+
+#[test]
+fn running_operations_lock_both_collections() {
+    let mut suite = setup();
+    suite.execute(ADMIN, &copy_msg(Some(2))).unwrap();
+
+    let err = suite.set(ALICE, "notes", "n6", "{}").unwrap_err();
+    assert!(error_text(err).contains("locked by a running operation on notes"));
+    assert!(suite.update(ALICE, "notes", "n1", r#"{"a":1}"#).is_err());
+    assert!(suite.delete(ALICE, "notes", "n5").is_err());
+    assert!(suite.set(ALICE, "archive", "a1", "{}").is_err());
+
+    suite.execute(ADMIN, &copy_msg(None)).unwrap();
+    suite.set(ALICE, "notes", "n6", "{}").unwrap();
+    suite.set(ALICE, "archive", "a1", "{}").unwrap();
+}
+
+#[test]
+fn settings_owners_and_pauses_follow_a_rename() {
+    let mut suite = setup();
+    suite.set_permissions("notes", owner_only());
+    suite.execute(ADMIN, &ExecuteMsg::SetCollectionOwner {
+        collection: "notes".to_string(),
+        owner: BOB.to_string(),
+        enabled: true,
+    })
+    .unwrap();
+    suite.execute(ADMIN, &ExecuteMsg::Pause {
+        scope: PauseScope::Collection("notes".to_string()),
+        reason: None,
+    })
+    .unwrap();
+
+    suite.execute(ADMIN, &rename_msg(None)).unwrap();
+
+    let permissions: CollectionPermissions =
+        suite.query(&QueryMsg::GetCollectionPermissions { collection: "archive".to_string() });
+    assert_eq!(permissions, owner_only());
+    let owners: Vec<Addr> = suite.query(&QueryMsg::ListCollectionOwners { collection: "archive".to_string() });
+    assert_eq!(owners, vec![Addr::unchecked(BOB)]);
+    let owners: Vec<Addr> = suite.query(&QueryMsg::ListCollectionOwners { collection: "notes".to_string() });
+    assert!(owners.is_empty());
+
+    let err = suite.set(ALICE, "archive", "a1", "{}").unwrap_err();
+    assert!(error_text(err).contains("Paused (collection:archive)"));
+    suite.set(ALICE, "notes", "n1", "{}").unwrap();
+}
+
+#[test]
+fn deposits_move_with_renamed_documents() {
+    let mut suite = Suite::with_balances(vec![(ALICE, coins(1_000, DENOM))]);
+    suite.execute(ADMIN, &ExecuteMsg::SetCollectionPricing {
+        collection: "notes".to_string(),
+        pricing: Some(CollectionPricing {
+            token: TokenDenom::Native(DENOM.to_string()),
+            write_fee: Uint128::zero(),
+            byte_fee: Uint128::zero(),
+            deposit: Uint128::new(100),
+        }),
+    })
+    .unwrap();
+    suite.execute_with_funds(ALICE, &ExecuteMsg::Set {
+        collection: "notes".to_string(),
+        document: "n1".to_string(),
+        data: "{}".to_string(),
+    }, &coins(100, DENOM))
+    .unwrap();
+
+    suite.execute(ADMIN, &rename_msg(None)).unwrap();
+    let deposit = |collection: &str| -> Option<Deposit> {
+        suite.query(&QueryMsg::GetDocumentDeposit { collection: collection.to_string(), document: "n1".to_string() })
+    };
+    assert!(deposit("notes").is_none());
+    assert_eq!(deposit("archive").unwrap().amount, Uint128::new(100));
+    // Nothing was refunded by the move itself
+    assert_eq!(suite.app.wrap().query_balance(ALICE, DENOM).unwrap().amount.u128(), 900);
+
+    suite.delete(ALICE, "archive", "n1").unwrap();
+    assert_eq!(suite.app.wrap().query_balance(ALICE, DENOM).unwrap().amount.u128(), 1_000);
+}
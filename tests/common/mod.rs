@@ -0,0 +1,148 @@
+// Shared cw-multi-test setup. Each test binary uses a different subset.
+#![allow(dead_code)]
+
+use cosmwasm_std::{Addr, Coin, Empty};
+use cw_multi_test::{App, AppBuilder, AppResponse, Contract, ContractWrapper, Executor};
+use serde::de::DeserializeOwned;
+
+use docustore::msg::{DocumentResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use docustore::state::{CollectionPermissions, Document, PermissionLevel};
+
+pub const ADMIN: &str = "admin";
+pub const ALICE: &str = "alice";
+pub const BOB: &str = "bob";
+pub const CAROL: &str = "carol";
+
+pub fn docustore_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        docustore::contract::execute,
+        docustore::contract::instantiate,
+        docustore::contract::query,
+    )
+    .with_reply(docustore::contract::reply);
+    Box::new(contract)
+}
+
+pub struct Suite {
+    pub app: App,
+    pub contract: Addr,
+}
+
+impl Suite {
+    pub fn new() -> Self {
+        Self::with_balances(vec![])
+    }
+
+    // Starts a chain where each (address, coins) pair is funded up front
+    pub fn with_balances(balances: Vec<(&str, Vec<Coin>)>) -> Self {
+        let mut app = AppBuilder::new().build(|router, _, storage| {
+            for (addr, coins) in balances {
+                router.bank.init_balance(storage, &Addr::unchecked(addr), coins).unwrap();
+            }
+        });
+        let code_id = app.store_code(docustore_contract());
+        let contract = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked(ADMIN),
+                &InstantiateMsg { admin: ADMIN.to_string() },
+                &[],
+                "docustore",
+                None,
+            )
+            .unwrap();
+        Suite { app, contract }
+    }
+
+    pub fn execute(&mut self, sender: &str, msg: &ExecuteMsg) -> anyhow::Result<AppResponse> {
+        self.app.execute_contract(Addr::unchecked(sender), self.contract.clone(), msg, &[])
+    }
+
+    pub fn execute_with_funds(&mut self, sender: &str, msg: &ExecuteMsg, funds: &[Coin]) -> anyhow::Result<AppResponse> {
+        self.app.execute_contract(Addr::unchecked(sender), self.contract.clone(), msg, funds)
+    }
+
+    pub fn query<T: DeserializeOwned>(&self, msg: &QueryMsg) -> T {
+        self.app.wrap().query_wasm_smart(self.contract.clone(), msg).unwrap()
+    }
+
+    pub fn set(&mut self, sender: &str, collection: &str, document: &str, data: &str) -> anyhow::Result<AppResponse> {
+        self.execute(sender, &ExecuteMsg::Set {
+            collection: collection.to_string(),
+            document: document.to_string(),
+            data: data.to_string(),
+        })
+    }
+
+    pub fn update(&mut self, sender: &str, collection: &str, document: &str, data: &str) -> anyhow::Result<AppResponse> {
+        self.execute(sender, &ExecuteMsg::Update {
+            collection: collection.to_string(),
+            document: document.to_string(),
+            data: data.to_string(),
+        })
+    }
+
+    pub fn delete(&mut self, sender: &str, collection: &str, document: &str) -> anyhow::Result<AppResponse> {
+        self.execute(sender, &ExecuteMsg::Delete {
+            collection: collection.to_string(),
+            document: document.to_string(),
+        })
+    }
+
+    pub fn get(&self, collection: &str, document: &str, viewer: Option<&str>) -> Option<Document> {
+        let response: DocumentResponse = self.query(&QueryMsg::Get {
+            collection: collection.to_string(),
+            document: document.to_string(),
+            viewer: viewer.map(str::to_string),
+        });
+        response.document
+    }
+
+    pub fn set_permissions(&mut self, collection: &str, permissions: CollectionPermissions) {
+        self.execute(ADMIN, &ExecuteMsg::SetCollectionPermissions {
+            collection: collection.to_string(),
+            permissions,
+        })
+        .unwrap();
+    }
+
+    pub fn grant_role(&mut self, user: &str, role: &str) {
+        self.execute(ADMIN, &ExecuteMsg::GrantRole {
+            user: user.to_string(),
+            role: role.to_string(),
+            expires_at: None,
+        })
+        .unwrap();
+    }
+
+    pub fn can(&self, collection: &str, document: &str, user: &str, action: &str) -> bool {
+        self.query(&QueryMsg::CheckDocumentPermission {
+            collection: collection.to_string(),
+            document: document.to_string(),
+            user: user.to_string(),
+            action: action.to_string(),
+        })
+    }
+
+    pub fn advance_seconds(&mut self, seconds: u64) {
+        self.app.update_block(|block| {
+            block.height += seconds / 5;
+            block.time = block.time.plus_seconds(seconds);
+        });
+    }
+}
+
+// Everyone may create and read, only owners may change their documents
+pub fn owner_only() -> CollectionPermissions {
+    CollectionPermissions {
+        create: PermissionLevel::Anyone,
+        update: PermissionLevel::OwnerOnly,
+        delete: PermissionLevel::OwnerOnly,
+        read: PermissionLevel::Anyone,
+    }
+}
+
+// The root cause message of a failed execute
+pub fn error_text(err: anyhow::Error) -> String {
+    err.root_cause().to_string()
+}
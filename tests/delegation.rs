@@ -0,0 +1,100 @@
+mod common;
+
+use cosmwasm_std::Addr;
+use docustore::msg::{ExecuteMsg, QueryMsg};
+
+use common::{error_text, owner_only, Suite, ADMIN, ALICE, BOB, CAROL};
+
+fn delegate_collection(suite: &mut Suite, owner: &str, enabled: bool) {
+    suite.execute(ADMIN, &ExecuteMsg::SetCollectionOwner {
+        collection: "notes".to_string(),
+        owner: owner.to_string(),
+        enabled,
+    })
+    .unwrap();
+}
+
+fn set_permissions(suite: &mut Suite, sender: &str, collection: &str) -> anyhow::Result<()> {
+    suite.execute(sender, &ExecuteMsg::SetCollectionPermissions {
+        collection: collection.to_string(),
+        permissions: owner_only(),
+    })
+    .map(|_| ())
+}
+
+#[test]
+fn collection_owners_manage_access_to_their_collection_only() {
+    let mut suite = Suite::new();
+    delegate_collection(&mut suite, ALICE, true);
+
+    set_permissions(&mut suite, ALICE, "notes").unwrap();
+    suite.execute(ALICE, &ExecuteMsg::SetFieldRules { collection: "notes".to_string(), rules: vec![] }).unwrap();
+    suite.execute(ALICE, &ExecuteMsg::SetCollectionRules { collection: "notes".to_string(), rules: None }).unwrap();
+    let err = set_permissions(&mut suite, ALICE, "other").unwrap_err();
+    assert!(error_text(err).contains("collection owner"));
+    assert!(set_permissions(&mut suite, BOB, "notes").is_err());
+
+    let owners: Vec<Addr> = suite.query(&QueryMsg::ListCollectionOwners { collection: "notes".to_string() });
+    assert_eq!(owners, vec![Addr::unchecked(ALICE)]);
+
+    delegate_collection(&mut suite, ALICE, false);
+    assert!(set_permissions(&mut suite, ALICE, "notes").is_err());
+}
+
+#[test]
+fn collection_owners_cannot_change_lifecycle_or_limits() {
+    let mut suite = Suite::new();
+    delegate_collection(&mut suite, ALICE, true);
+
+    let admin_only = [
+        ExecuteMsg::SetCollectionWorkflow { collection: "notes".to_string(), workflow: None },
+        ExecuteMsg::SetCollectionQuota { collection: "notes".to_string(), quota: None },
+        ExecuteMsg::SetCollectionRateLimit { collection: "notes".to_string(), limit: None },
+    ];
+    for msg in &admin_only {
+        let err = suite.execute(ALICE, msg).unwrap_err();
+        assert!(error_text(err).contains("Only admin"));
+        suite.execute(ADMIN, msg).unwrap();
+    }
+}
+
+#[test]
+fn role_managers_grant_and_revoke_their_role_only() {
+    let mut suite = Suite::new();
+    suite.execute(ADMIN, &ExecuteMsg::SetRoleManager {
+        role: "editor".to_string(),
+        manager: ALICE.to_string(),
+        enabled: true,
+    })
+    .unwrap();
+
+    let grant = |role: &str| ExecuteMsg::GrantRole { user: CAROL.to_string(), role: role.to_string(), expires_at: None };
+    suite.execute(ALICE, &grant("editor")).unwrap();
+    let err = suite.execute(ALICE, &grant("moderator")).unwrap_err();
+    assert!(error_text(err).contains("role manager"));
+    assert!(suite.execute(BOB, &grant("editor")).is_err());
+
+    suite.execute(ALICE, &ExecuteMsg::RevokeRole { user: CAROL.to_string(), role: "editor".to_string() }).unwrap();
+    let roles: Vec<String> = suite.query(&QueryMsg::GetUserRoles { user: CAROL.to_string() });
+    assert!(roles.is_empty());
+
+    // Managers cannot appoint further managers
+    let err = suite.execute(ALICE, &ExecuteMsg::SetRoleManager {
+        role: "editor".to_string(),
+        manager: BOB.to_string(),
+        enabled: true,
+    })
+    .unwrap_err();
+    assert!(error_text(err).contains("Only admin"));
+}
+
+#[test]
+fn delegations_outlive_the_switch_to_an_admin_set() {
+    let mut suite = Suite::new();
+    delegate_collection(&mut suite, ALICE, true);
+    suite.execute(ADMIN, &ExecuteMsg::SetAdminSet { members: vec![BOB.to_string(), CAROL.to_string()], threshold: 2 })
+        .unwrap();
+
+    set_permissions(&mut suite, ALICE, "notes").unwrap();
+    assert!(set_permissions(&mut suite, ADMIN, "notes").is_err());
+}
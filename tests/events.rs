@@ -0,0 +1,67 @@
+mod common;
+
+use cw_multi_test::AppResponse;
+use sha2::{Digest, Sha256};
+
+use docustore::msg::{ExecuteMsg, WriteOperation, WriteType};
+
+use common::{Suite, ALICE};
+
+// Attributes of every docustore_write event in emission order, without the
+// contract address the chain adds
+fn write_events(response: &AppResponse) -> Vec<Vec<(String, String)>> {
+    response.events.iter()
+        .filter(|event| event.ty == "wasm-docustore_write")
+        .map(|event| {
+            event.attributes.iter()
+                .filter(|attr| attr.key != "_contract_address")
+                .map(|attr| (attr.key.clone(), attr.value.clone()))
+                .collect()
+        })
+        .collect()
+}
+
+fn expected(document: &str, op: &str, version: u64, data: Option<&str>) -> Vec<(String, String)> {
+    let mut attributes = vec![
+        ("collection", "notes".to_string()),
+        ("document", document.to_string()),
+        ("op", op.to_string()),
+        ("actor", ALICE.to_string()),
+        ("version", version.to_string()),
+        ("size", data.map(str::len).unwrap_or_default().to_string()),
+    ];
+    if let Some(data) = data {
+        attributes.push(("hash", hex(&Sha256::digest(data.as_bytes()))));
+    }
+    attributes.into_iter().map(|(key, value)| (key.to_string(), value)).collect()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn operation(document: &str, operation: WriteType) -> WriteOperation {
+    WriteOperation { collection: "notes".to_string(), document: document.to_string(), operation }
+}
+
+#[test]
+fn batch_writes_emit_one_event_per_operation() {
+    let mut suite = Suite::new();
+    suite.set(ALICE, "notes", "n1", r#"{"a":1}"#).unwrap();
+    suite.set(ALICE, "notes", "n3", "{}").unwrap();
+
+    let response = suite.execute(ALICE, &ExecuteMsg::BatchWrite {
+        operations: vec![
+            operation("n2", WriteType::Set { data: r#"{"a":2}"#.to_string() }),
+            operation("n1", WriteType::Update { data: r#"{"b":1}"#.to_string() }),
+            operation("n3", WriteType::Delete),
+        ],
+    })
+    .unwrap();
+
+    assert_eq!(write_events(&response), vec![
+        expected("n2", "set", 3, Some(r#"{"a":2}"#)),
+        expected("n1", "update", 4, Some(r#"{"a":1,"b":1}"#)),
+        expected("n3", "delete", 5, None),
+    ]);
+}
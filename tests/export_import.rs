@@ -0,0 +1,147 @@
+mod common;
+
+use cosmwasm_std::{coins, Uint128};
+use docustore::msg::{ExecuteMsg, ExportRecord, ExportResponse, QueryMsg, EXPORT_VERSION};
+use docustore::state::{CollectionPermissions, CollectionPricing, Deposit, ImportProgress, PauseScope, TokenDenom};
+
+use common::{error_text, owner_only, Suite, ADMIN, ALICE, BOB};
+
+// Permissions for one collection, one role grant and three documents
+fn source() -> Suite {
+    let mut suite = Suite::new();
+    suite.set_permissions("notes", owner_only());
+    suite.grant_role(BOB, "editor");
+    suite.set(ALICE, "notes", "n1", r#"{"a":1}"#).unwrap();
+    suite.set(ALICE, "notes", "n2", r#"{"a":2}"#).unwrap();
+    suite.set(BOB, "notes", "n3", r#"{"a":3}"#).unwrap();
+    suite
+}
+
+// Pages through the whole export, returning every page
+fn export(suite: &Suite, limit: u32) -> Vec<ExportResponse> {
+    let mut pages: Vec<ExportResponse> = Vec::new();
+    loop {
+        let previous = pages.last();
+        let page: ExportResponse = suite.query(&QueryMsg::Export {
+            cursor: previous.and_then(|page| page.next_cursor.clone()),
+            checksum: previous.map(|page| page.checksum.clone()),
+            records_total: previous.map(|page| page.records_total),
+            limit: Some(limit),
+        });
+        let done = page.next_cursor.is_none();
+        pages.push(page);
+        if done {
+            return pages;
+        }
+    }
+}
+
+fn import(suite: &mut Suite, records: Vec<ExportRecord>) -> anyhow::Result<()> {
+    suite.execute(ADMIN, &ExecuteMsg::Import { version: EXPORT_VERSION, records }).map(|_| ())
+}
+
+fn finish(suite: &mut Suite, last: &ExportResponse) -> anyhow::Result<()> {
+    suite.execute(ADMIN, &ExecuteMsg::FinishImport {
+        records: last.records_total,
+        checksum: last.checksum.clone(),
+    })
+    .map(|_| ())
+}
+
+#[test]
+fn a_paged_export_imports_into_a_fresh_contract() {
+    let original = source();
+    let pages = export(&original, 2);
+    assert_eq!(pages.len(), 3);
+    let last = pages.last().unwrap().clone();
+    assert_eq!(last.records_total, 5);
+    // A single page gives the same checksum as the paged walk
+    assert_eq!(export(&original, 100)[0].checksum, last.checksum);
+
+    let mut restored = Suite::new();
+    for page in pages {
+        import(&mut restored, page.records).unwrap();
+    }
+    finish(&mut restored, &last).unwrap();
+
+    for id in ["n1", "n2", "n3"] {
+        assert_eq!(restored.get("notes", id, None), original.get("notes", id, None));
+    }
+    let permissions: CollectionPermissions = restored.query(&QueryMsg::GetCollectionPermissions {
+        collection: "notes".to_string(),
+    });
+    assert_eq!(permissions, owner_only());
+    let roles: Vec<String> = restored.query(&QueryMsg::GetUserRoles { user: BOB.to_string() });
+    assert_eq!(roles, vec!["editor".to_string()]);
+    let progress: ImportProgress = restored.query(&QueryMsg::GetImportProgress {});
+    assert_eq!(progress, ImportProgress::default());
+}
+
+#[test]
+fn finishing_a_partial_import_fails() {
+    let original = source();
+    let pages = export(&original, 2);
+    let last = pages.last().unwrap().clone();
+
+    let mut restored = Suite::new();
+    import(&mut restored, pages[0].records.clone()).unwrap();
+    let err = finish(&mut restored, &last).unwrap_err();
+    assert!(error_text(err).contains("Import incomplete"));
+}
+
+#[test]
+fn only_the_admin_imports_the_current_version() {
+    let original = source();
+    let records = export(&original, 100).remove(0).records;
+
+    let mut restored = Suite::new();
+    assert!(restored.execute(ALICE, &ExecuteMsg::Import { version: EXPORT_VERSION, records: records.clone() }).is_err());
+    let err = restored.execute(ADMIN, &ExecuteMsg::Import { version: EXPORT_VERSION + 1, records }).unwrap_err();
+    assert!(error_text(err).contains("Unsupported export version"));
+}
+
+#[test]
+fn imports_respect_pauses() {
+    let original = source();
+    let records = export(&original, 100).remove(0).records;
+
+    let mut restored = Suite::new();
+    restored.execute(ADMIN, &ExecuteMsg::Pause {
+        scope: PauseScope::Collection("notes".to_string()),
+        reason: None,
+    })
+    .unwrap();
+    assert!(import(&mut restored, records).is_err());
+    assert!(restored.get("notes", "n1", None).is_none());
+}
+
+#[test]
+fn overwriting_a_document_refunds_its_deposit() {
+    let records = export(&source(), 100).remove(0).records;
+
+    let mut restored = Suite::with_balances(vec![(ALICE, coins(1_000, "uxion"))]);
+    restored.execute(ADMIN, &ExecuteMsg::SetCollectionPricing {
+        collection: "notes".to_string(),
+        pricing: Some(CollectionPricing {
+            token: TokenDenom::Native("uxion".to_string()),
+            write_fee: Uint128::zero(),
+            byte_fee: Uint128::zero(),
+            deposit: Uint128::new(100),
+        }),
+    })
+    .unwrap();
+    restored.execute_with_funds(ALICE, &ExecuteMsg::Set {
+        collection: "notes".to_string(),
+        document: "n1".to_string(),
+        data: "{}".to_string(),
+    }, &coins(100, "uxion"))
+    .unwrap();
+
+    import(&mut restored, records).unwrap();
+    let deposit: Option<Deposit> = restored.query(&QueryMsg::GetDocumentDeposit {
+        collection: "notes".to_string(),
+        document: "n1".to_string(),
+    });
+    assert!(deposit.is_none());
+    assert_eq!(restored.app.wrap().query_balance(ALICE, "uxion").unwrap().amount.u128(), 1_000);
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use docustore::msg::{CollectionResponse, ExecuteMsg, QueryMsg, SearchResponse};
+use docustore::state::{FieldRule, PermissionLevel};
+
+use common::{error_text, Suite, ADMIN, ALICE, BOB, CAROL};
+
+const PROFILE: &str = r#"{"name":"alice","email":"alice@example.com","contact":{"phone":"555"}}"#;
+
+// Email and phone are readable by the owner only, `verified` is written by
+// verifiers only
+fn setup() -> Suite {
+    let mut suite = Suite::new();
+    suite.execute(ADMIN, &ExecuteMsg::SetFieldRules {
+        collection: "profiles".to_string(),
+        rules: vec![
+            FieldRule { path: "email".to_string(), read: Some(PermissionLevel::OwnerOnly), write: None },
+            FieldRule { path: "contact.phone".to_string(), read: Some(PermissionLevel::OwnerOnly), write: None },
+            FieldRule {
+                path: "verified".to_string(),
+                read: None,
+                write: Some(PermissionLevel::RequireRole("verifier".to_string())),
+            },
+        ],
+    })
+    .unwrap();
+    suite.grant_role(CAROL, "verifier");
+    suite.set(ALICE, "profiles", "p1", PROFILE).unwrap();
+    suite
+}
+
+fn data(suite: &Suite, viewer: Option<&str>) -> serde_json::Value {
+    let doc = suite.get("profiles", "p1", viewer).unwrap();
+    serde_json::from_str(&doc.data).unwrap()
+}
+
+#[test]
+fn write_rules_reject_changes_to_protected_paths() {
+    let mut suite = setup();
+    let err = suite.update(ALICE, "profiles", "p1", r#"{"verified":true}"#).unwrap_err();
+    assert_eq!(error_text(err), "Generic error: Unauthorized: Cannot write field verified");
+
+    suite.update(CAROL, "profiles", "p1", r#"{"verified":true}"#).unwrap();
+    // Writes that leave the protected value alone still go through
+    suite.update(ALICE, "profiles", "p1", r#"{"name":"alice b"}"#).unwrap();
+
+    // Overwriting without the field removes it, which counts as a write
+    let err = suite.set(ALICE, "profiles", "p1", PROFILE).unwrap_err();
+    assert!(error_text(err).contains("Cannot write field verified"));
+    suite.set(ADMIN, "profiles", "p1", PROFILE).unwrap();
+}
+
+#[test]
+fn field_paths_must_not_have_empty_segments() {
+    let mut suite = Suite::new();
+    let err = suite.execute(ADMIN, &ExecuteMsg::SetFieldRules {
+        collection: "profiles".to_string(),
+        rules: vec![FieldRule { path: "contact..phone".to_string(), read: None, write: None }],
+    })
+    .unwrap_err();
+    assert!(error_text(err).contains("Invalid field path: contact..phone"));
+}
+
+#[test]
+fn get_strips_fields_the_viewer_cannot_read() {
+    let suite = setup();
+    for viewer in [None, Some(BOB)] {
+        let value = data(&suite, viewer);
+        assert_eq!(value["name"], "alice");
+        assert!(value.get("email").is_none());
+        assert!(value["contact"].get("phone").is_none());
+    }
+    for viewer in [ALICE, ADMIN] {
+        let value = data(&suite, Some(viewer));
+        assert_eq!(value["email"], "alice@example.com");
+        assert_eq!(value["contact"]["phone"], "555");
+    }
+}
+
+#[test]
+fn listings_strip_fields_the_viewer_cannot_read() {
+    let suite = setup();
+    let listed: CollectionResponse = suite.query(&QueryMsg::Collection {
+        collection: "profiles".to_string(),
+        limit: None,
+        start_after: None,
+        viewer: Some(BOB.to_string()),
+    });
+    assert!(!listed.documents[0].1.data.contains("alice@example.com"));
+
+    let owned = |viewer: &str| -> CollectionResponse {
+        suite.query(&QueryMsg::UserDocuments {
+            owner: ALICE.to_string(),
+            collection: Some("profiles".to_string()),
+            limit: None,
+            start_after: None,
+            viewer: Some(viewer.to_string()),
+        })
+    };
+    assert!(!owned(BOB).documents[0].1.data.contains("alice@example.com"));
+    assert!(owned(ALICE).documents[0].1.data.contains("alice@example.com"));
+}
+
+#[test]
+fn search_never_matches_fields_the_viewer_cannot_read() {
+    let suite = setup();
+    for viewer in [None, Some(BOB)] {
+        let found: SearchResponse = suite.query(&QueryMsg::SearchDocuments {
+            collection: "profiles".to_string(),
+            query: r#"{"email":"alice@example.com"}"#.to_string(),
+            limit: None,
+            start_after: None,
+            viewer: viewer.map(str::to_string),
+        });
+        assert!(found.results.is_empty());
+    }
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult,
+};
+use cw4::{Cw4QueryMsg, MemberResponse};
+use cw_multi_test::{Contract, ContractWrapper, Executor};
+use cw_storage_plus::Map;
+use serde::{Deserialize, Serialize};
+
+use docustore::msg::QueryMsg;
+use docustore::state::{CollectionPermissions, PermissionLevel};
+
+use common::{Suite, ADMIN, ALICE, BOB, CAROL};
+
+// Minimal cw4 group stand-in: weights by address and the Member query the
+// permission check makes
+const WEIGHTS: Map<&str, u64> = Map::new("weights");
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct MembersMsg {
+    members: Vec<(String, Option<u64>)>,  // (addr, weight), None removes the member
+}
+
+fn group_instantiate(deps: DepsMut, _env: Env, _info: MessageInfo, msg: MembersMsg) -> StdResult<Response> {
+    update_members(deps, msg)
+}
+
+fn group_execute(deps: DepsMut, _env: Env, _info: MessageInfo, msg: MembersMsg) -> StdResult<Response> {
+    update_members(deps, msg)
+}
+
+fn update_members(deps: DepsMut, msg: MembersMsg) -> StdResult<Response> {
+    for (addr, weight) in msg.members {
+        match weight {
+            Some(weight) => WEIGHTS.save(deps.storage, &addr, &weight)?,
+            None => WEIGHTS.remove(deps.storage, &addr),
+        }
+    }
+    Ok(Response::new())
+}
+
+fn group_query(deps: Deps, _env: Env, msg: Cw4QueryMsg) -> StdResult<Binary> {
+    match msg {
+        Cw4QueryMsg::Member { addr, .. } => to_json_binary(&MemberResponse {
+            weight: WEIGHTS.may_load(deps.storage, &addr)?,
+        }),
+        _ => Err(StdError::generic_err("Unsupported")),
+    }
+}
+
+fn group_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(group_execute, group_instantiate, group_query))
+}
+
+// Alice weighs 5, Bob 1, Carol is not a member
+fn setup() -> (Suite, Addr) {
+    let mut suite = Suite::new();
+    let code_id = suite.app.store_code(group_contract());
+    let group = suite.app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(ADMIN),
+            &MembersMsg { members: vec![(ALICE.to_string(), Some(5)), (BOB.to_string(), Some(1))] },
+            &[],
+            "group",
+            None,
+        )
+        .unwrap();
+    (suite, group)
+}
+
+fn gate(suite: &mut Suite, group: &Addr, min_weight: u64) {
+    suite.set_permissions("council", CollectionPermissions {
+        create: PermissionLevel::Cw4Member { group_addr: group.to_string(), min_weight },
+        update: PermissionLevel::OwnerOnly,
+        delete: PermissionLevel::OwnerOnly,
+        read: PermissionLevel::Anyone,
+    });
+}
+
+fn can_create(suite: &Suite, user: &str) -> bool {
+    suite.query(&QueryMsg::CheckPermission {
+        collection: "council".to_string(),
+        user: user.to_string(),
+        action: "create".to_string(),
+    })
+}
+
+fn set_weight(suite: &mut Suite, group: &Addr, user: &str, weight: Option<u64>) {
+    suite.app
+        .execute_contract(
+            Addr::unchecked(ADMIN),
+            group.clone(),
+            &MembersMsg { members: vec![(user.to_string(), weight)] },
+            &[],
+        )
+        .unwrap();
+}
+
+#[test]
+fn members_need_at_least_min_weight() {
+    let (mut suite, group) = setup();
+    gate(&mut suite, &group, 2);
+
+    suite.set(ALICE, "council", "a1", "{}").unwrap();
+    assert!(suite.set(BOB, "council", "b1", "{}").is_err());
+    assert!(!can_create(&suite, CAROL));
+}
+
+#[test]
+fn zero_weight_members_still_count_as_members() {
+    let (mut suite, group) = setup();
+    gate(&mut suite, &group, 0);
+    set_weight(&mut suite, &group, BOB, Some(0));
+
+    assert!(can_create(&suite, BOB));
+    assert!(!can_create(&suite, CAROL));
+}
+
+#[test]
+fn weight_changes_apply_on_the_next_check() {
+    let (mut suite, group) = setup();
+    gate(&mut suite, &group, 2);
+
+    set_weight(&mut suite, &group, BOB, Some(3));
+    assert!(can_create(&suite, BOB));
+    set_weight(&mut suite, &group, ALICE, None);
+    assert!(!can_create(&suite, ALICE));
+    assert!(suite.set(ALICE, "council", "a1", "{}").is_err());
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, Event, MessageInfo, Response, StdError, StdResult,
+};
+use cw_multi_test::{AppResponse, Contract, ContractWrapper, Executor};
+use cw_storage_plus::Item;
+use serde::{Deserialize, Serialize};
+
+use docustore::msg::{DocustoreHookMsg, ExecuteMsg};
+use docustore::state::{ChangeOp, HookMode};
+
+use common::{Suite, ADMIN, ALICE};
+
+// Hook stand-in: records every notification it accepts and rejects any
+// write to a document whose id starts with "fail"
+const RECEIVED: Item<Vec<DocustoreHookMsg>> = Item::new("received");
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+enum HookMsg {
+    DocustoreHook(DocustoreHookMsg),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct ReceivedQuery {}
+
+fn hook_instantiate(deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+    RECEIVED.save(deps.storage, &vec![])?;
+    Ok(Response::new())
+}
+
+fn hook_execute(deps: DepsMut, _env: Env, _info: MessageInfo, msg: HookMsg) -> StdResult<Response> {
+    let HookMsg::DocustoreHook(hook) = msg;
+    if hook.change.document.starts_with("fail") {
+        return Err(StdError::generic_err("hook rejected the write"));
+    }
+    RECEIVED.update(deps.storage, |mut received| -> StdResult<_> {
+        received.push(hook);
+        Ok(received)
+    })?;
+    Ok(Response::new())
+}
+
+fn hook_query(deps: Deps, _env: Env, _msg: ReceivedQuery) -> StdResult<Binary> {
+    to_json_binary(&RECEIVED.load(deps.storage)?)
+}
+
+fn hook_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(hook_execute, hook_instantiate, hook_query))
+}
+
+fn with_hook(mode: HookMode) -> (Suite, Addr) {
+    let mut suite = Suite::new();
+    let code_id = suite.app.store_code(hook_contract());
+    let hook = suite.app
+        .instantiate_contract(code_id, Addr::unchecked(ADMIN), &Empty {}, &[], "hook", None)
+        .unwrap();
+    suite.execute(ADMIN, &ExecuteMsg::AddHook {
+        collection: "notes".to_string(),
+        contract: hook.to_string(),
+        mode,
+    })
+    .unwrap();
+    (suite, hook)
+}
+
+fn received(suite: &Suite, hook: &Addr) -> Vec<DocustoreHookMsg> {
+    suite.app.wrap().query_wasm_smart(hook, &ReceivedQuery {}).unwrap()
+}
+
+fn hook_events<'a>(response: &'a AppResponse, op: &'a str) -> impl Iterator<Item = &'a Event> + 'a {
+    response.events.iter()
+        .filter(|event| event.ty == "wasm-docustore_hook")
+        .filter(move |event| event.attributes.iter().any(|attr| attr.key == "op" && attr.value == op))
+}
+
+#[test]
+fn hooks_receive_each_write_in_the_collection() {
+    let (mut suite, hook) = with_hook(HookMode::Required);
+    let response = suite.set(ALICE, "notes", "n1", "{}").unwrap();
+    assert_eq!(hook_events(&response, "dispatch").count(), 1);
+    suite.update(ALICE, "notes", "n1", r#"{"a":1}"#).unwrap();
+    suite.set(ALICE, "other", "o1", "{}").unwrap();
+
+    let received = received(&suite, &hook);
+    let ops: Vec<ChangeOp> = received.iter().map(|msg| msg.change.op.clone()).collect();
+    assert_eq!(ops, vec![ChangeOp::Set, ChangeOp::Update]);
+    assert_eq!(received[0].actor, Addr::unchecked(ALICE));
+    assert_eq!(received[0].change.document, "n1");
+}
+
+#[test]
+fn a_failing_required_hook_reverts_the_write() {
+    let (mut suite, _) = with_hook(HookMode::Required);
+    assert!(suite.set(ALICE, "notes", "fail1", "{}").is_err());
+    assert!(suite.get("notes", "fail1", None).is_none());
+}
+
+#[test]
+fn a_failing_best_effort_hook_leaves_the_write_in_place() {
+    let (mut suite, hook) = with_hook(HookMode::BestEffort);
+    let response = suite.set(ALICE, "notes", "fail1", "{}").unwrap();
+    assert!(suite.get("notes", "fail1", None).is_some());
+    assert!(received(&suite, &hook).is_empty());
+
+    let failed: Vec<_> = hook_events(&response, "failed").collect();
+    assert_eq!(failed.len(), 1);
+    assert!(failed[0].attributes.iter().any(|attr| attr.key == "error" && attr.value.contains("hook rejected the write")));
+}
+
+#[test]
+fn removed_hooks_are_no_longer_called() {
+    let (mut suite, hook) = with_hook(HookMode::Required);
+    suite.execute(ADMIN, &ExecuteMsg::RemoveHook {
+        collection: "notes".to_string(),
+        contract: hook.to_string(),
+    })
+    .unwrap();
+    let response = suite.set(ALICE, "notes", "fail1", "{}").unwrap();
+    assert_eq!(hook_events(&response, "dispatch").count(), 0);
+    assert!(received(&suite, &hook).is_empty());
+}
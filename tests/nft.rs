@@ -0,0 +1,180 @@
+mod common;
+
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response, StdError, StdResult,
+};
+use cw721::{Cw721ExecuteMsg, Cw721QueryMsg, OwnerOfResponse, TokensResponse};
+use cw_multi_test::{Contract, ContractWrapper, Executor};
+use cw_storage_plus::Map;
+use serde::{Deserialize, Serialize};
+
+use docustore::msg::QueryMsg;
+use docustore::state::{CollectionPermissions, PermissionLevel};
+
+use common::{Suite, ADMIN, ALICE, BOB};
+
+// Minimal cw721 stand-in: owners by token id, transfers and the two queries
+// the permission check makes
+const OWNERS: Map<&str, Addr> = Map::new("owners");
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct MintMsg {
+    tokens: Vec<(String, String)>,  // (token_id, owner)
+}
+
+fn nft_instantiate(deps: DepsMut, _env: Env, _info: MessageInfo, msg: MintMsg) -> StdResult<Response> {
+    for (token_id, owner) in msg.tokens {
+        OWNERS.save(deps.storage, &token_id, &Addr::unchecked(owner))?;
+    }
+    Ok(Response::new())
+}
+
+fn nft_execute(deps: DepsMut, _env: Env, info: MessageInfo, msg: Cw721ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        Cw721ExecuteMsg::TransferNft { recipient, token_id } => {
+            if OWNERS.load(deps.storage, &token_id)? != info.sender {
+                return Err(StdError::generic_err("Not the token owner"));
+            }
+            OWNERS.save(deps.storage, &token_id, &Addr::unchecked(recipient))?;
+            Ok(Response::new())
+        }
+        Cw721ExecuteMsg::Burn { token_id } => {
+            OWNERS.remove(deps.storage, &token_id);
+            Ok(Response::new())
+        }
+        _ => Err(StdError::generic_err("Unsupported")),
+    }
+}
+
+fn nft_query(deps: Deps, _env: Env, msg: Cw721QueryMsg) -> StdResult<Binary> {
+    match msg {
+        Cw721QueryMsg::OwnerOf { token_id, .. } => to_json_binary(&OwnerOfResponse {
+            owner: OWNERS.load(deps.storage, &token_id)?.to_string(),
+            approvals: vec![],
+        }),
+        Cw721QueryMsg::Tokens { owner, limit, .. } => {
+            let tokens = OWNERS
+                .range(deps.storage, None, None, Order::Ascending)
+                .filter(|item| item.as_ref().map_or(true, |(_, holder)| holder.as_str() == owner))
+                .map(|item| item.map(|(token_id, _)| token_id))
+                .take(limit.unwrap_or(10) as usize)
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&TokensResponse { tokens })
+        }
+        _ => Err(StdError::generic_err("Unsupported")),
+    }
+}
+
+fn nft_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(nft_execute, nft_instantiate, nft_query))
+}
+
+// Alice holds "gold" and "silver", Bob holds nothing
+fn setup() -> (Suite, Addr) {
+    let mut suite = Suite::new();
+    let code_id = suite.app.store_code(nft_contract());
+    let nft = suite.app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(ADMIN),
+            &MintMsg {
+                tokens: vec![
+                    ("gold".to_string(), ALICE.to_string()),
+                    ("silver".to_string(), ALICE.to_string()),
+                ],
+            },
+            &[],
+            "nft",
+            None,
+        )
+        .unwrap();
+    (suite, nft)
+}
+
+fn gate(suite: &mut Suite, collection: &str, level: PermissionLevel) {
+    suite.set_permissions(collection, CollectionPermissions {
+        create: level,
+        update: PermissionLevel::OwnerOnly,
+        delete: PermissionLevel::OwnerOnly,
+        read: PermissionLevel::Anyone,
+    });
+}
+
+fn holder_of(nft: &Addr, token_id: Option<&str>, min_count: Option<u32>) -> PermissionLevel {
+    PermissionLevel::Cw721Holder {
+        collection_addr: nft.to_string(),
+        token_id: token_id.map(str::to_string),
+        min_count,
+    }
+}
+
+fn can_create(suite: &Suite, collection: &str, user: &str) -> bool {
+    suite.query(&QueryMsg::CheckPermission {
+        collection: collection.to_string(),
+        user: user.to_string(),
+        action: "create".to_string(),
+    })
+}
+
+fn transfer(suite: &mut Suite, nft: &Addr, sender: &str, recipient: &str, token_id: &str) {
+    suite.app
+        .execute_contract(
+            Addr::unchecked(sender),
+            nft.clone(),
+            &Cw721ExecuteMsg::TransferNft { recipient: recipient.to_string(), token_id: token_id.to_string() },
+            &[],
+        )
+        .unwrap();
+}
+
+#[test]
+fn holders_pass_and_non_holders_fail() {
+    let (mut suite, nft) = setup();
+    gate(&mut suite, "club", holder_of(&nft, None, None));
+
+    suite.set(ALICE, "club", "a1", "{}").unwrap();
+    assert!(suite.set(BOB, "club", "b1", "{}").is_err());
+}
+
+#[test]
+fn a_specific_token_follows_its_transfer() {
+    let (mut suite, nft) = setup();
+    gate(&mut suite, "vault", holder_of(&nft, Some("gold"), None));
+    assert!(can_create(&suite, "vault", ALICE));
+    assert!(!can_create(&suite, "vault", BOB));
+
+    transfer(&mut suite, &nft, ALICE, BOB, "gold");
+    assert!(!can_create(&suite, "vault", ALICE));
+    suite.set(BOB, "vault", "b1", "{}").unwrap();
+    assert!(suite.set(ALICE, "vault", "a1", "{}").is_err());
+}
+
+#[test]
+fn min_count_needs_that_many_tokens() {
+    let (mut suite, nft) = setup();
+    gate(&mut suite, "whales", holder_of(&nft, None, Some(2)));
+    assert!(can_create(&suite, "whales", ALICE));
+
+    transfer(&mut suite, &nft, ALICE, BOB, "silver");
+    assert!(!can_create(&suite, "whales", ALICE));
+    assert!(!can_create(&suite, "whales", BOB));
+}
+
+#[test]
+fn burned_tokens_are_held_by_nobody() {
+    let (mut suite, nft) = setup();
+    gate(&mut suite, "vault", holder_of(&nft, Some("gold"), None));
+    suite.app
+        .execute_contract(Addr::unchecked(ALICE), nft.clone(), &Cw721ExecuteMsg::Burn { token_id: "gold".to_string() }, &[])
+        .unwrap();
+    assert!(!can_create(&suite, "vault", ALICE));
+}
+
+#[test]
+fn failing_owner_queries_do_not_pass_a_negated_check() {
+    let (mut suite, _) = setup();
+    // The docustore contract does not answer OwnerOf
+    let not_a_collection = suite.contract.clone();
+    gate(&mut suite, "outsiders", PermissionLevel::Not(Box::new(holder_of(&not_a_collection, Some("gold"), None))));
+    assert!(suite.set(BOB, "outsiders", "b1", "{}").is_err());
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use docustore::msg::{ExecuteMsg, WriteOperation, WriteType};
+use docustore::state::{PauseScope, WriteKind};
+
+use common::{error_text, Suite, ADMIN, ALICE, BOB};
+
+fn pause(suite: &mut Suite, sender: &str, scope: PauseScope) -> anyhow::Result<()> {
+    suite.execute(sender, &ExecuteMsg::Pause { scope, reason: Some("incident".to_string()) }).map(|_| ())
+}
+
+fn unpause(suite: &mut Suite, scope: PauseScope) {
+    suite.execute(ADMIN, &ExecuteMsg::Unpause { scope }).unwrap();
+}
+
+#[test]
+fn a_contract_pause_stops_every_write_but_not_reads() {
+    let mut suite = Suite::new();
+    suite.set(ALICE, "notes", "n1", "{}").unwrap();
+    pause(&mut suite, ADMIN, PauseScope::Contract).unwrap();
+
+    let err = suite.set(ALICE, "notes", "n2", "{}").unwrap_err();
+    assert_eq!(error_text(err), "Generic error: Paused (contract): incident");
+    assert!(suite.update(ALICE, "notes", "n1", "{}").is_err());
+    assert!(suite.delete(ALICE, "notes", "n1").is_err());
+    let batch = ExecuteMsg::BatchWrite {
+        operations: vec![WriteOperation {
+            collection: "other".to_string(),
+            document: "o1".to_string(),
+            operation: WriteType::Set { data: "{}".to_string() },
+        }],
+    };
+    assert!(suite.execute(ALICE, &batch).is_err());
+    assert!(suite.get("notes", "n1", None).is_some());
+
+    unpause(&mut suite, PauseScope::Contract);
+    suite.set(ALICE, "notes", "n2", "{}").unwrap();
+}
+
+#[test]
+fn collection_and_write_kind_pauses_are_scoped() {
+    let mut suite = Suite::new();
+    suite.set(ALICE, "notes", "n1", "{}").unwrap();
+
+    pause(&mut suite, ADMIN, PauseScope::Collection("notes".to_string())).unwrap();
+    assert!(suite.set(ALICE, "notes", "n2", "{}").is_err());
+    suite.set(ALICE, "other", "o1", "{}").unwrap();
+    unpause(&mut suite, PauseScope::Collection("notes".to_string()));
+
+    pause(&mut suite, ADMIN, PauseScope::Write(WriteKind::Delete)).unwrap();
+    assert!(suite.delete(ALICE, "notes", "n1").is_err());
+    suite.update(ALICE, "notes", "n1", r#"{"a":1}"#).unwrap();
+}
+
+#[test]
+fn admin_set_members_pause_alone_but_only_the_admin_unpauses() {
+    let mut suite = Suite::new();
+    assert!(pause(&mut suite, ALICE, PauseScope::Contract).is_err());
+
+    suite.execute(ADMIN, &ExecuteMsg::SetAdminSet {
+        members: vec![ALICE.to_string(), BOB.to_string()],
+        threshold: 2,
+    })
+    .unwrap();
+    pause(&mut suite, ALICE, PauseScope::Contract).unwrap();
+    assert!(suite.execute(ALICE, &ExecuteMsg::Unpause { scope: PauseScope::Contract }).is_err());
+}
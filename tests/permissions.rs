@@ -0,0 +1,110 @@
+mod common;
+
+use docustore::msg::ExecuteMsg;
+use docustore::state::{
+    CollectionPermissions, PermissionLevel, MAX_PERMISSION_DEPTH, MAX_PERMISSION_NODES, MAX_PERMISSION_QUERIES,
+};
+
+use common::{error_text, Suite, ADMIN, ALICE, BOB, CAROL};
+
+fn role(name: &str) -> PermissionLevel {
+    PermissionLevel::RequireRole(name.to_string())
+}
+
+// Owners or editors update, moderators delete unless suspended. Owners
+// always manage their own documents, so the checks are made on others.
+fn setup() -> Suite {
+    let mut suite = Suite::new();
+    suite.set_permissions("notes", CollectionPermissions {
+        create: PermissionLevel::Anyone,
+        update: PermissionLevel::Any(vec![PermissionLevel::OwnerOnly, role("editor")]),
+        delete: PermissionLevel::All(vec![
+            role("moderator"),
+            PermissionLevel::Not(Box::new(role("suspended"))),
+        ]),
+        read: PermissionLevel::Anyone,
+    });
+    suite.grant_role(CAROL, "editor");
+    suite.set(ALICE, "notes", "n1", r#"{"text":"hi"}"#).unwrap();
+    suite
+}
+
+fn set_update(suite: &mut Suite, update: PermissionLevel) -> anyhow::Result<()> {
+    suite.execute(ADMIN, &ExecuteMsg::SetCollectionPermissions {
+        collection: "notes".to_string(),
+        permissions: CollectionPermissions { update, ..CollectionPermissions::default() },
+    })
+    .map(|_| ())
+}
+
+#[test]
+fn any_allows_when_one_branch_matches() {
+    let mut suite = setup();
+    assert!(suite.can("notes", "n1", ALICE, "update"));
+    assert!(suite.can("notes", "n1", CAROL, "update"));
+    assert!(!suite.can("notes", "n1", BOB, "update"));
+
+    suite.update(CAROL, "notes", "n1", r#"{"text":"edited"}"#).unwrap();
+    assert!(suite.update(BOB, "notes", "n1", r#"{"text":"bob"}"#).is_err());
+}
+
+#[test]
+fn all_requires_every_branch_and_not_inverts() {
+    let mut suite = setup();
+    // Editors pass Not(suspended) but are not moderators
+    assert!(!suite.can("notes", "n1", CAROL, "delete"));
+    suite.grant_role(BOB, "moderator");
+    assert!(suite.can("notes", "n1", BOB, "delete"));
+
+    suite.grant_role(BOB, "suspended");
+    assert!(!suite.can("notes", "n1", BOB, "delete"));
+    assert!(suite.delete(BOB, "notes", "n1").is_err());
+}
+
+#[test]
+fn owner_only_never_matches_without_a_document() {
+    let mut suite = Suite::new();
+    suite.set_permissions("notes", CollectionPermissions {
+        create: PermissionLevel::OwnerOnly,
+        ..CollectionPermissions::default()
+    });
+    let err = suite.set(ALICE, "notes", "n1", r#"{"text":"hi"}"#).unwrap_err();
+    assert!(error_text(err).contains("Insufficient permissions"));
+}
+
+#[test]
+fn expressions_deeper_than_the_limit_are_rejected() {
+    let mut suite = setup();
+    let mut level = PermissionLevel::Anyone;
+    for _ in 1..MAX_PERMISSION_DEPTH {
+        level = PermissionLevel::Not(Box::new(level));
+    }
+    set_update(&mut suite, level.clone()).unwrap();
+
+    let err = set_update(&mut suite, PermissionLevel::Not(Box::new(level))).unwrap_err();
+    assert!(error_text(err).contains("max depth"));
+}
+
+#[test]
+fn expressions_larger_than_the_limit_are_rejected() {
+    let mut suite = setup();
+    let leaves = MAX_PERMISSION_NODES as usize - 1;
+    set_update(&mut suite, PermissionLevel::Any(vec![PermissionLevel::Anyone; leaves])).unwrap();
+
+    let err = set_update(&mut suite, PermissionLevel::Any(vec![PermissionLevel::Anyone; leaves + 1])).unwrap_err();
+    assert!(error_text(err).contains("max size"));
+
+    let err = set_update(&mut suite, PermissionLevel::All(vec![])).unwrap_err();
+    assert!(error_text(err).contains("cannot be empty"));
+}
+
+#[test]
+fn expressions_with_too_many_contract_queries_are_rejected() {
+    let mut suite = setup();
+    let group = |n: u32| PermissionLevel::Cw4Member { group_addr: format!("group{}", n), min_weight: 1 };
+    let queries = MAX_PERMISSION_QUERIES;
+    set_update(&mut suite, PermissionLevel::Any((0..queries).map(group).collect())).unwrap();
+
+    let err = set_update(&mut suite, PermissionLevel::Any((0..=queries).map(group).collect())).unwrap_err();
+    assert!(error_text(err).contains("contract queries"));
+}
@@ -0,0 +1,162 @@
+mod common;
+
+use cosmwasm_std::{coins, to_json_binary, Addr, Uint128};
+use cw20::Cw20ReceiveMsg;
+use docustore::msg::ExecuteMsg;
+use docustore::state::{CollectionPricing, TokenDenom};
+
+use common::{error_text, Suite, ADMIN, ALICE, BOB};
+
+const DENOM: &str = "uxion";
+// Stands in for a cw20 contract, Receive only checks who sends it
+const TOKEN: &str = "token";
+
+fn accept_cw20(suite: &mut Suite, contract: &str, enabled: bool) {
+    suite.execute(ADMIN, &ExecuteMsg::SetAcceptedCw20 { contract: contract.to_string(), enabled }).unwrap();
+}
+
+fn pricing_in(token: TokenDenom) -> CollectionPricing {
+    CollectionPricing {
+        token,
+        write_fee: Uint128::new(10),
+        byte_fee: Uint128::zero(),
+        deposit: Uint128::new(100),
+    }
+}
+
+fn priced(token: TokenDenom) -> Suite {
+    let mut suite = Suite::with_balances(vec![(ALICE, coins(1_000, DENOM))]);
+    if let TokenDenom::Cw20(contract) = &token {
+        accept_cw20(&mut suite, contract, true);
+    }
+    suite.execute(ADMIN, &ExecuteMsg::SetCollectionPricing {
+        collection: "ads".to_string(),
+        pricing: Some(pricing_in(token)),
+    })
+    .unwrap();
+    suite
+}
+
+fn set_msg(document: &str) -> ExecuteMsg {
+    ExecuteMsg::Set {
+        collection: "ads".to_string(),
+        document: document.to_string(),
+        data: "{}".to_string(),
+    }
+}
+
+fn balance(suite: &Suite, addr: &str) -> u128 {
+    suite.app.wrap().query_balance(addr, DENOM).unwrap().amount.u128()
+}
+
+fn receive(suite: &mut Suite, token: &str, amount: u128, msg: &ExecuteMsg) -> anyhow::Result<()> {
+    receive_from(suite, token, ALICE, amount, msg)
+}
+
+// The token contract alone decides the sender it reports
+fn receive_from(suite: &mut Suite, token: &str, sender: &str, amount: u128, msg: &ExecuteMsg) -> anyhow::Result<()> {
+    let wrapper = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(amount),
+        msg: to_json_binary(msg).unwrap(),
+    });
+    suite.execute(token, &wrapper).map(|_| ())
+}
+
+#[test]
+fn creates_pay_the_fee_and_the_deposit() {
+    let mut suite = priced(TokenDenom::Native(DENOM.to_string()));
+    let err = suite.execute(ALICE, &set_msg("a1")).unwrap_err();
+    assert!(error_text(err).contains("Insufficient payment"));
+
+    suite.execute_with_funds(ALICE, &set_msg("a1"), &coins(110, DENOM)).unwrap();
+    assert_eq!(balance(&suite, ALICE), 890);
+    assert_eq!(balance(&suite, suite.contract.as_str()), 110);
+
+    // Overwriting only pays the write fee
+    suite.execute_with_funds(ALICE, &set_msg("a1"), &coins(10, DENOM)).unwrap();
+    assert_eq!(balance(&suite, ALICE), 880);
+}
+
+#[test]
+fn overpaying_is_rejected() {
+    let mut suite = priced(TokenDenom::Native(DENOM.to_string()));
+    let err = suite.execute_with_funds(ALICE, &set_msg("a1"), &coins(120, DENOM)).unwrap_err();
+    assert!(error_text(err).contains("exceeds price"));
+    assert_eq!(balance(&suite, ALICE), 1_000);
+}
+
+#[test]
+fn deleting_refunds_the_deposit_and_fees_stay_withdrawable() {
+    let mut suite = priced(TokenDenom::Native(DENOM.to_string()));
+    suite.execute_with_funds(ALICE, &set_msg("a1"), &coins(110, DENOM)).unwrap();
+    suite.delete(ALICE, "ads", "a1").unwrap();
+    assert_eq!(balance(&suite, ALICE), 990);
+
+    suite.execute(ADMIN, &ExecuteMsg::WithdrawFees {
+        token: TokenDenom::Native(DENOM.to_string()),
+        amount: None,
+        recipient: None,
+    })
+    .unwrap();
+    assert_eq!(balance(&suite, ADMIN), 10);
+    assert_eq!(balance(&suite, suite.contract.as_str()), 0);
+}
+
+#[test]
+fn cw20_receive_only_from_the_pricing_token() {
+    let mut suite = priced(TokenDenom::Cw20(TOKEN.to_string()));
+
+    let err = receive(&mut suite, "impostor", 110, &set_msg("a1")).unwrap_err();
+    assert!(error_text(err).contains("not an accepted cw20"));
+    let err = receive(&mut suite, TOKEN, 0, &set_msg("a1")).unwrap_err();
+    assert!(error_text(err).contains("non-zero amount"));
+
+    receive(&mut suite, TOKEN, 110, &set_msg("a1")).unwrap();
+    let doc = suite.get("ads", "a1", None).unwrap();
+    assert_eq!(doc.owner, Addr::unchecked(ALICE));
+}
+
+#[test]
+fn cw20_receive_rejects_unpriced_collections() {
+    let mut suite = priced(TokenDenom::Cw20(TOKEN.to_string()));
+    let free = ExecuteMsg::Set {
+        collection: "free".to_string(),
+        document: "f1".to_string(),
+        data: "{}".to_string(),
+    };
+    let err = receive(&mut suite, TOKEN, 1, &free).unwrap_err();
+    assert!(error_text(err).contains("not priced in token"));
+}
+
+#[test]
+fn owner_chosen_tokens_cannot_impersonate_the_admin() {
+    let mut suite = Suite::new();
+    suite.execute(ADMIN, &ExecuteMsg::SetCollectionOwner {
+        collection: "ads".to_string(),
+        owner: BOB.to_string(),
+        enabled: true,
+    })
+    .unwrap();
+
+    // Bob's own token could report any sender
+    let set_pricing = |token: &str| ExecuteMsg::SetCollectionPricing {
+        collection: "ads".to_string(),
+        pricing: Some(pricing_in(TokenDenom::Cw20(token.to_string()))),
+    };
+    let err = suite.execute(BOB, &set_pricing("bobtoken")).unwrap_err();
+    assert!(error_text(err).contains("not an accepted cw20"));
+    let err = receive_from(&mut suite, "bobtoken", ADMIN, 110, &set_msg("a1")).unwrap_err();
+    assert!(error_text(err).contains("not an accepted cw20"));
+    assert!(suite.get("ads", "a1", None).is_none());
+
+    // Owners still set amounts in tokens the admin accepts
+    accept_cw20(&mut suite, TOKEN, true);
+    suite.execute(BOB, &set_pricing(TOKEN)).unwrap();
+    receive(&mut suite, TOKEN, 110, &set_msg("a1")).unwrap();
+
+    // Dropping a token stops its Receive even where pricing still names it
+    accept_cw20(&mut suite, TOKEN, false);
+    let err = receive(&mut suite, TOKEN, 10, &set_msg("a1")).unwrap_err();
+    assert!(error_text(err).contains("not an accepted cw20"));
+}
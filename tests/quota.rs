@@ -0,0 +1,60 @@
+mod common;
+
+use docustore::msg::{ExecuteMsg, QueryMsg, UserUsageResponse};
+use docustore::state::{Quota, Usage};
+
+use common::{error_text, Suite, ADMIN, ALICE, BOB};
+
+fn usage(suite: &Suite, user: &str) -> UserUsageResponse {
+    suite.query(&QueryMsg::GetUserUsage { user: user.to_string(), collection: Some("notes".to_string()) })
+}
+
+fn with_quota(quota: Quota) -> Suite {
+    let mut suite = Suite::new();
+    suite.execute(ADMIN, &ExecuteMsg::SetCollectionQuota {
+        collection: "notes".to_string(),
+        quota: Some(quota),
+    })
+    .unwrap();
+    suite
+}
+
+#[test]
+fn document_counts_are_limited_per_owner() {
+    let mut suite = with_quota(Quota { max_documents: Some(2), ..Quota::default() });
+    suite.set(ALICE, "notes", "n1", "{}").unwrap();
+    suite.set(ALICE, "notes", "n2", "{}").unwrap();
+    let err = suite.set(ALICE, "notes", "n3", "{}").unwrap_err();
+    assert!(error_text(err).contains("max 2 documents"));
+
+    // Other owners have their own allowance, deleting frees a slot
+    suite.set(BOB, "notes", "b1", "{}").unwrap();
+    suite.delete(ALICE, "notes", "n1").unwrap();
+    suite.set(ALICE, "notes", "n3", "{}").unwrap();
+    assert_eq!(usage(&suite, ALICE).remaining_documents, Some(0));
+}
+
+#[test]
+fn bytes_and_document_size_are_limited() {
+    let mut suite = with_quota(Quota { max_bytes: Some(20), max_document_size: Some(12), ..Quota::default() });
+    let err = suite.set(ALICE, "notes", "n1", r#"{"text":"toolong"}"#).unwrap_err();
+    assert!(error_text(err).contains("document size"));
+
+    suite.set(ALICE, "notes", "n1", r#"{"a":"1234"}"#).unwrap();
+    let err = suite.set(ALICE, "notes", "n2", r#"{"b":"1234"}"#).unwrap_err();
+    assert!(error_text(err).contains("bytes per owner"));
+    assert_eq!(usage(&suite, ALICE).usage, Usage { documents: 1, bytes: 12 });
+}
+
+#[test]
+fn shrinking_writes_pass_under_a_lowered_quota() {
+    let mut suite = Suite::new();
+    suite.set(ALICE, "notes", "n1", r#"{"text":"a long note"}"#).unwrap();
+    suite.execute(ADMIN, &ExecuteMsg::SetGlobalQuota {
+        quota: Some(Quota { max_bytes: Some(5), ..Quota::default() }),
+    })
+    .unwrap();
+
+    suite.set(ALICE, "notes", "n1", r#"{"t":1}"#).unwrap();
+    assert!(suite.set(ALICE, "notes", "n2", "{}").is_err());
+}
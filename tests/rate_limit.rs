@@ -0,0 +1,75 @@
+mod common;
+
+use docustore::msg::{ExecuteMsg, QueryMsg, RateLimitStatusResponse};
+use docustore::ContractError;
+use docustore::state::{RateLimit, RateWindow};
+
+use common::{error_text, Suite, ADMIN, ALICE, BOB};
+
+// Two writes per 10 blocks, editors are exempt
+fn limited() -> Suite {
+    let mut suite = Suite::new();
+    suite.execute(ADMIN, &ExecuteMsg::SetCollectionRateLimit {
+        collection: "chat".to_string(),
+        limit: Some(RateLimit {
+            max_writes: 2,
+            window: RateWindow::Blocks(10),
+            exempt_roles: vec!["editor".to_string()],
+        }),
+    })
+    .unwrap();
+    suite
+}
+
+fn status(suite: &Suite, user: &str) -> RateLimitStatusResponse {
+    suite.query(&QueryMsg::GetRateLimitStatus { collection: "chat".to_string(), user: user.to_string() })
+}
+
+fn advance_blocks(suite: &mut Suite, blocks: u64) {
+    suite.app.update_block(|block| block.height += blocks);
+}
+
+#[test]
+fn writes_beyond_the_bucket_are_rejected_until_it_refills() {
+    let mut suite = limited();
+    suite.set(ALICE, "chat", "m1", "{}").unwrap();
+    suite.set(ALICE, "chat", "m2", "{}").unwrap();
+    assert_eq!(status(&suite, ALICE).remaining_writes, Some(0));
+
+    let err = suite.set(ALICE, "chat", "m3", "{}").unwrap_err();
+    let height = suite.app.block_info().height;
+    match err.downcast_ref::<ContractError>() {
+        Some(ContractError::RateLimited { retry_after, unit }) => {
+            assert_eq!(unit, "height");
+            assert_eq!(*retry_after, height + 5);
+        }
+        other => panic!("expected a rate limit error, got {:?}", other),
+    }
+    assert!(error_text(err).starts_with("Rate limited: retry after height"));
+    // Buckets are per sender
+    suite.set(BOB, "chat", "b1", "{}").unwrap();
+
+    // One write refills every 5 blocks
+    advance_blocks(&mut suite, 5);
+    suite.set(ALICE, "chat", "m3", "{}").unwrap();
+    assert!(suite.set(ALICE, "chat", "m4", "{}").is_err());
+}
+
+#[test]
+fn deletes_count_against_the_same_bucket() {
+    let mut suite = limited();
+    suite.set(ALICE, "chat", "m1", "{}").unwrap();
+    suite.delete(ALICE, "chat", "m1").unwrap();
+    assert!(suite.set(ALICE, "chat", "m2", "{}").is_err());
+}
+
+#[test]
+fn admin_and_exempt_roles_are_not_limited() {
+    let mut suite = limited();
+    suite.grant_role(BOB, "editor");
+    for i in 0..5 {
+        suite.set(ADMIN, "chat", &format!("a{}", i), "{}").unwrap();
+        suite.set(BOB, "chat", &format!("b{}", i), "{}").unwrap();
+    }
+    assert!(status(&suite, BOB).exempt);
+}
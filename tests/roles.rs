@@ -0,0 +1,189 @@
+mod common;
+
+use cosmwasm_std::Timestamp;
+use docustore::msg::{ExecuteMsg, QueryMsg, RoleGrantInfo, RoleListResponse, RoleMembersResponse};
+use docustore::state::{CollectionPermissions, PermissionLevel};
+
+use common::{Suite, ADMIN, ALICE, BOB, CAROL};
+
+// Only holders of `role` may create documents in "reports"
+fn gated_by(suite: &mut Suite, role: &str) {
+    suite.set_permissions("reports", CollectionPermissions {
+        create: PermissionLevel::RequireRole(role.to_string()),
+        update: PermissionLevel::OwnerOnly,
+        delete: PermissionLevel::OwnerOnly,
+        read: PermissionLevel::Anyone,
+    });
+}
+
+fn can_create(suite: &Suite, user: &str) -> bool {
+    suite.query(&QueryMsg::CheckPermission {
+        collection: "reports".to_string(),
+        user: user.to_string(),
+        action: "create".to_string(),
+    })
+}
+
+fn grant_until(suite: &mut Suite, user: &str, role: &str, expires_at: Timestamp) {
+    suite.execute(ADMIN, &ExecuteMsg::GrantRole {
+        user: user.to_string(),
+        role: role.to_string(),
+        expires_at: Some(expires_at),
+    })
+    .unwrap();
+}
+
+#[test]
+fn expired_grants_stop_counting_at_their_expiry() {
+    let mut suite = Suite::new();
+    gated_by(&mut suite, "auditor");
+    let expires_at = suite.app.block_info().time.plus_seconds(100);
+    grant_until(&mut suite, ALICE, "auditor", expires_at);
+
+    assert!(can_create(&suite, ALICE));
+    suite.set(ALICE, "reports", "r1", "{}").unwrap();
+
+    suite.advance_seconds(100);
+    assert!(!can_create(&suite, ALICE));
+    assert!(suite.set(ALICE, "reports", "r2", "{}").is_err());
+    let roles: Vec<String> = suite.query(&QueryMsg::GetUserRoles { user: ALICE.to_string() });
+    assert!(roles.is_empty());
+}
+
+#[test]
+fn grants_must_expire_in_the_future() {
+    let mut suite = Suite::new();
+    let now = suite.app.block_info().time;
+    let granted = suite.execute(ADMIN, &ExecuteMsg::GrantRole {
+        user: ALICE.to_string(),
+        role: "auditor".to_string(),
+        expires_at: Some(now),
+    });
+    assert!(granted.is_err());
+}
+
+#[test]
+fn anyone_can_prune_expired_grants() {
+    let mut suite = Suite::new();
+    let expires_at = suite.app.block_info().time.plus_seconds(10);
+    grant_until(&mut suite, ALICE, "auditor", expires_at);
+    suite.grant_role(ALICE, "reader");
+
+    // Nothing has expired yet
+    suite.execute("bob", &ExecuteMsg::PruneExpiredRoles { user: ALICE.to_string() }).unwrap();
+    suite.advance_seconds(10);
+    suite.execute("bob", &ExecuteMsg::PruneExpiredRoles { user: ALICE.to_string() }).unwrap();
+
+    let grants: Vec<RoleGrantInfo> = suite.query(&QueryMsg::ListUserRoleGrants { user: ALICE.to_string() });
+    let roles: Vec<_> = grants.iter().map(|info| info.role.as_str()).collect();
+    assert_eq!(roles, vec!["reader"]);
+}
+
+fn set_inherits(suite: &mut Suite, role: &str, inherits: &[&str]) -> anyhow::Result<()> {
+    suite.execute(ADMIN, &ExecuteMsg::SetRoleInherits {
+        role: role.to_string(),
+        inherits: inherits.iter().map(|role| role.to_string()).collect(),
+    })
+    .map(|_| ())
+}
+
+#[test]
+fn inherited_roles_satisfy_role_checks() {
+    let mut suite = Suite::new();
+    gated_by(&mut suite, "editor");
+    set_inherits(&mut suite, "moderator", &["editor"]).unwrap();
+    set_inherits(&mut suite, "owner", &["moderator"]).unwrap();
+    suite.grant_role(ALICE, "owner");
+
+    assert!(can_create(&suite, ALICE));
+    let mut roles: Vec<String> = suite.query(&QueryMsg::GetEffectiveRoles { user: ALICE.to_string() });
+    roles.sort();
+    assert_eq!(roles, vec!["editor", "moderator", "owner"]);
+
+    // Removing a link cuts off everything below it
+    set_inherits(&mut suite, "moderator", &[]).unwrap();
+    assert!(!can_create(&suite, ALICE));
+}
+
+#[test]
+fn inheritance_cycles_are_rejected() {
+    let mut suite = Suite::new();
+    set_inherits(&mut suite, "a", &["b"]).unwrap();
+    set_inherits(&mut suite, "b", &["c"]).unwrap();
+    assert!(set_inherits(&mut suite, "c", &["a"]).is_err());
+}
+
+#[test]
+fn inherited_roles_expire_with_the_granted_role() {
+    let mut suite = Suite::new();
+    gated_by(&mut suite, "editor");
+    set_inherits(&mut suite, "moderator", &["editor"]).unwrap();
+    let expires_at = suite.app.block_info().time.plus_seconds(30);
+    grant_until(&mut suite, ALICE, "moderator", expires_at);
+
+    assert!(can_create(&suite, ALICE));
+    suite.advance_seconds(30);
+    assert!(!can_create(&suite, ALICE));
+}
+
+fn members(suite: &Suite, role: &str, limit: Option<u32>, start_after: Option<String>) -> RoleMembersResponse {
+    suite.query(&QueryMsg::ListRoleMembers { role: role.to_string(), limit, start_after })
+}
+
+fn role_counts(suite: &Suite, limit: Option<u32>, start_after: Option<String>) -> (Vec<(String, u64)>, Option<String>) {
+    let page: RoleListResponse = suite.query(&QueryMsg::ListRoles { limit, start_after });
+    let counts = page.roles.into_iter().map(|info| (info.role, info.member_count)).collect();
+    (counts, page.next_start_after)
+}
+
+fn counted(counts: &[(&str, u64)]) -> Vec<(String, u64)> {
+    counts.iter().map(|(role, count)| (role.to_string(), *count)).collect()
+}
+
+#[test]
+fn role_members_page_by_address() {
+    let mut suite = Suite::new();
+    for user in [CAROL, ALICE, BOB] {
+        suite.grant_role(user, "editor");
+    }
+
+    let page = members(&suite, "editor", Some(2), None);
+    let users: Vec<_> = page.members.iter().map(|m| m.user.as_str()).collect();
+    assert_eq!(users, vec![ALICE, BOB]);
+    assert_eq!(page.next_start_after.as_deref(), Some(BOB));
+
+    let page = members(&suite, "editor", Some(2), page.next_start_after);
+    let users: Vec<_> = page.members.iter().map(|m| m.user.as_str()).collect();
+    assert_eq!(users, vec![CAROL]);
+    assert_eq!(page.next_start_after, None);
+}
+
+#[test]
+fn role_counts_follow_grants_revokes_and_pruning() {
+    let mut suite = Suite::new();
+    suite.grant_role(ALICE, "editor");
+    suite.grant_role(BOB, "editor");
+    // Granting a held role again does not count twice
+    suite.grant_role(BOB, "editor");
+    suite.grant_role(ALICE, "auditor");
+    let expires_at = suite.app.block_info().time.plus_seconds(10);
+    grant_until(&mut suite, CAROL, "viewer", expires_at);
+
+    let (counts, next) = role_counts(&suite, Some(2), None);
+    assert_eq!(counts, counted(&[("auditor", 1), ("editor", 2)]));
+    let (counts, next) = role_counts(&suite, Some(2), next);
+    assert_eq!(counts, counted(&[("viewer", 1)]));
+    assert_eq!(next, None);
+
+    suite.execute(ADMIN, &ExecuteMsg::RevokeRole { user: BOB.to_string(), role: "editor".to_string() }).unwrap();
+    suite.execute(ADMIN, &ExecuteMsg::RevokeRole { user: ALICE.to_string(), role: "auditor".to_string() }).unwrap();
+    assert_eq!(members(&suite, "editor", None, None).members.len(), 1);
+
+    // Expired members drop out of the listing at once, the count waits for a prune
+    suite.advance_seconds(10);
+    assert!(members(&suite, "viewer", None, None).members.is_empty());
+    assert_eq!(role_counts(&suite, None, None).0, counted(&[("editor", 1), ("viewer", 1)]));
+
+    suite.execute(BOB, &ExecuteMsg::PruneExpiredRoles { user: CAROL.to_string() }).unwrap();
+    assert_eq!(role_counts(&suite, None, None).0, counted(&[("editor", 1)]));
+}
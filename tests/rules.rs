@@ -0,0 +1,76 @@
+mod common;
+
+use cosmwasm_std::{from_json, to_json_vec};
+use docustore::msg::ExecuteMsg;
+use docustore::rules::{compile, CollectionRules, MAX_RULE_DEPTH};
+
+use common::{owner_only, Suite, ADMIN, ALICE, BOB};
+
+fn and_chain(terms: u32) -> String {
+    let terms = vec!["true"; terms as usize];
+    format!("allow update: if {};", terms.join(" && "))
+}
+
+#[test]
+fn compiled_rules_survive_a_storage_round_trip() {
+    let rules = compile("allow read, update: if request.auth == resource.owner && request.data.title.size() < 100;").unwrap();
+    let stored: CollectionRules = from_json(to_json_vec(&rules).unwrap()).unwrap();
+    assert_eq!(stored, rules);
+}
+
+#[test]
+fn deepest_allowed_chain_survives_a_storage_round_trip() {
+    // Each term adds one level to the left-associative tree
+    let rules = compile(&and_chain(MAX_RULE_DEPTH)).unwrap();
+    let stored: CollectionRules = from_json(to_json_vec(&rules).unwrap()).unwrap();
+    assert_eq!(stored, rules);
+}
+
+#[test]
+fn long_chains_are_rejected_before_they_are_stored() {
+    let err = compile(&and_chain(200)).unwrap_err();
+    assert!(err.to_string().contains("nested deeper"), "{}", err);
+}
+
+#[test]
+fn chains_one_past_the_limit_are_rejected() {
+    assert!(compile(&and_chain(MAX_RULE_DEPTH + 1)).is_err());
+}
+
+fn rules_suite(source: &str) -> Suite {
+    let mut suite = Suite::new();
+    suite.set_permissions("posts", owner_only());
+    suite.execute(ADMIN, &ExecuteMsg::SetCollectionRules {
+        collection: "posts".to_string(),
+        rules: Some(source.to_string()),
+    })
+    .unwrap();
+    suite
+}
+
+#[test]
+fn create_rules_see_the_incoming_data() {
+    let mut suite = rules_suite("allow create: if request.data.title.size() < 10;");
+    suite.set(ALICE, "posts", "p1", r#"{"title":"short"}"#).unwrap();
+    assert!(suite.set(ALICE, "posts", "p2", r#"{"title":"much too long"}"#).is_err());
+}
+
+#[test]
+fn update_rules_see_the_merged_document() {
+    let mut suite = rules_suite(
+        "allow create: if true; allow update: if request.auth == resource.owner && request.data.title.size() < 10;",
+    );
+    suite.set(ALICE, "posts", "p1", r#"{"title":"short"}"#).unwrap();
+
+    // The update doesn't carry the title, the merged document does
+    suite.update(ALICE, "posts", "p1", r#"{"body":"text"}"#).unwrap();
+    assert!(suite.update(ALICE, "posts", "p1", r#"{"title":"much too long"}"#).is_err());
+    assert!(suite.update(BOB, "posts", "p1", r#"{"body":"bob"}"#).is_err());
+}
+
+#[test]
+fn floats_in_document_data_read_as_null() {
+    let mut suite = rules_suite("allow create: if request.data.score == null && request.data.count == 3;");
+    suite.set(ALICE, "posts", "p1", r#"{"score":2.5,"count":3}"#).unwrap();
+    assert!(suite.set(ALICE, "posts", "p2", r#"{"score":2,"count":3}"#).is_err());
+}
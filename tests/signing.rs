@@ -0,0 +1,112 @@
+mod common;
+
+use docustore::msg::{ExecuteMsg, QueryMsg, SignatureRequestListResponse};
+use docustore::state::{
+    CollectionWorkflow, PermissionLevel, SignatureDecision, SignatureRequest, SignatureStatus, WorkflowTransition,
+};
+
+use common::{error_text, Suite, ADMIN, ALICE, BOB, CAROL};
+
+const DAVE: &str = "dave";
+
+fn request(suite: &mut Suite, threshold: u32) -> u64 {
+    suite.set(ALICE, "contracts", "c1", r#"{"terms":"v1"}"#).unwrap();
+    suite.execute(ALICE, &ExecuteMsg::RequestSignatures {
+        collection: "contracts".to_string(),
+        document: "c1".to_string(),
+        signers: vec![BOB.to_string(), CAROL.to_string(), DAVE.to_string()],
+        threshold,
+        content_hash: None,
+    })
+    .unwrap();
+    let list: SignatureRequestListResponse = suite.query(&QueryMsg::ListSignatureRequests {
+        collection: "contracts".to_string(),
+        document: "c1".to_string(),
+        limit: None,
+        start_after: None,
+    });
+    list.requests.last().unwrap().id
+}
+
+fn sign(suite: &mut Suite, signer: &str, request_id: u64, decision: SignatureDecision) -> anyhow::Result<()> {
+    suite.execute(signer, &ExecuteMsg::RecordSignature { request_id, decision }).map(|_| ())
+}
+
+fn status(suite: &Suite, request_id: u64) -> SignatureStatus {
+    let request: SignatureRequest = suite.query(&QueryMsg::GetSignatureRequest { request_id });
+    request.status
+}
+
+#[test]
+fn request_executes_once_the_threshold_is_reached() {
+    let mut suite = Suite::new();
+    let id = request(&mut suite, 2);
+
+    sign(&mut suite, BOB, id, SignatureDecision::Sign).unwrap();
+    assert_eq!(status(&suite, id), SignatureStatus::Pending);
+    sign(&mut suite, CAROL, id, SignatureDecision::Sign).unwrap();
+    assert_eq!(status(&suite, id), SignatureStatus::Executed);
+
+    let err = sign(&mut suite, DAVE, id, SignatureDecision::Sign).unwrap_err();
+    assert!(error_text(err).contains("no longer pending"));
+}
+
+#[test]
+fn request_is_rejected_once_the_threshold_is_out_of_reach() {
+    let mut suite = Suite::new();
+    let id = request(&mut suite, 2);
+
+    sign(&mut suite, BOB, id, SignatureDecision::Reject).unwrap();
+    assert_eq!(status(&suite, id), SignatureStatus::Pending);
+    sign(&mut suite, CAROL, id, SignatureDecision::Reject).unwrap();
+    assert_eq!(status(&suite, id), SignatureStatus::Rejected);
+}
+
+#[test]
+fn only_listed_signers_sign_and_only_once() {
+    let mut suite = Suite::new();
+    let id = request(&mut suite, 2);
+
+    let err = sign(&mut suite, ALICE, id, SignatureDecision::Sign).unwrap_err();
+    assert!(error_text(err).contains("Not a signer"));
+    sign(&mut suite, BOB, id, SignatureDecision::Sign).unwrap();
+    let err = sign(&mut suite, BOB, id, SignatureDecision::Sign).unwrap_err();
+    assert!(error_text(err).contains("already recorded"));
+}
+
+#[test]
+fn writing_the_document_invalidates_pending_requests() {
+    let mut suite = Suite::new();
+    let id = request(&mut suite, 2);
+
+    suite.update(ALICE, "contracts", "c1", r#"{"terms":"v2"}"#).unwrap();
+    assert_eq!(status(&suite, id), SignatureStatus::Invalidated);
+}
+
+#[test]
+fn moving_the_document_to_another_state_invalidates_pending_requests() {
+    let mut suite = Suite::new();
+    suite.execute(ADMIN, &ExecuteMsg::SetCollectionWorkflow {
+        collection: "contracts".to_string(),
+        workflow: Some(CollectionWorkflow {
+            states: vec!["draft".to_string(), "final".to_string()],
+            initial_state: "draft".to_string(),
+            transitions: vec![WorkflowTransition {
+                from: "draft".to_string(),
+                to: "final".to_string(),
+                permission: PermissionLevel::OwnerOnly,
+            }],
+            state_permissions: vec![],
+        }),
+    })
+    .unwrap();
+    let id = request(&mut suite, 2);
+
+    suite.execute(ALICE, &ExecuteMsg::Transition {
+        collection: "contracts".to_string(),
+        document: "c1".to_string(),
+        to_state: "final".to_string(),
+    })
+    .unwrap();
+    assert_eq!(status(&suite, id), SignatureStatus::Invalidated);
+}
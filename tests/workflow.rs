@@ -0,0 +1,94 @@
+mod common;
+
+use docustore::msg::ExecuteMsg;
+use docustore::state::{CollectionWorkflow, PermissionLevel, StatePermissions, WorkflowTransition};
+
+use common::{error_text, owner_only, Suite, ADMIN, ALICE, BOB, CAROL};
+
+fn transition(from: &str, to: &str, permission: PermissionLevel) -> WorkflowTransition {
+    WorkflowTransition { from: from.to_string(), to: to.to_string(), permission }
+}
+
+// draft -> review by the owner, review -> published by editors. Drafts are
+// private and published documents are frozen.
+fn setup() -> Suite {
+    let mut suite = Suite::new();
+    suite.set_permissions("articles", owner_only());
+    suite.execute(ADMIN, &ExecuteMsg::SetCollectionWorkflow {
+        collection: "articles".to_string(),
+        workflow: Some(CollectionWorkflow {
+            states: vec!["draft".to_string(), "review".to_string(), "published".to_string()],
+            initial_state: "draft".to_string(),
+            transitions: vec![
+                transition("draft", "review", PermissionLevel::OwnerOnly),
+                transition("review", "published", PermissionLevel::RequireRole("editor".to_string())),
+            ],
+            state_permissions: vec![
+                StatePermissions { state: "draft".to_string(), read: Some(PermissionLevel::OwnerOnly), update: None },
+                StatePermissions { state: "published".to_string(), read: None, update: Some(PermissionLevel::AdminOnly) },
+            ],
+        }),
+    })
+    .unwrap();
+    suite.grant_role(CAROL, "editor");
+    suite.set(ALICE, "articles", "a1", r#"{"title":"hello"}"#).unwrap();
+    suite
+}
+
+fn move_to(suite: &mut Suite, sender: &str, to_state: &str) -> anyhow::Result<()> {
+    suite.execute(sender, &ExecuteMsg::Transition {
+        collection: "articles".to_string(),
+        document: "a1".to_string(),
+        to_state: to_state.to_string(),
+    })
+    .map(|_| ())
+}
+
+#[test]
+fn new_documents_start_in_the_initial_state() {
+    let suite = setup();
+    let doc = suite.get("articles", "a1", Some(ALICE)).unwrap();
+    assert_eq!(doc.status.as_deref(), Some("draft"));
+}
+
+#[test]
+fn transitions_follow_the_edges_and_their_permissions() {
+    let mut suite = setup();
+
+    let err = move_to(&mut suite, ALICE, "published").unwrap_err();
+    assert!(error_text(err).contains("not allowed"));
+    let err = move_to(&mut suite, BOB, "review").unwrap_err();
+    assert!(error_text(err).contains("Insufficient permissions"));
+
+    move_to(&mut suite, ALICE, "review").unwrap();
+    let err = move_to(&mut suite, ALICE, "published").unwrap_err();
+    assert!(error_text(err).contains("Insufficient permissions"));
+    move_to(&mut suite, CAROL, "published").unwrap();
+
+    let doc = suite.get("articles", "a1", None).unwrap();
+    assert_eq!(doc.status.as_deref(), Some("published"));
+}
+
+#[test]
+fn state_read_overrides_hide_documents_from_get() {
+    let mut suite = setup();
+    assert!(suite.get("articles", "a1", Some(BOB)).is_none());
+    assert!(suite.get("articles", "a1", None).is_none());
+    assert!(suite.get("articles", "a1", Some(ALICE)).is_some());
+
+    move_to(&mut suite, ALICE, "review").unwrap();
+    assert!(suite.get("articles", "a1", Some(BOB)).is_some());
+}
+
+#[test]
+fn state_update_overrides_apply_to_the_owner() {
+    let mut suite = setup();
+    move_to(&mut suite, ALICE, "review").unwrap();
+    move_to(&mut suite, CAROL, "published").unwrap();
+
+    assert!(!suite.can("articles", "a1", ALICE, "update"));
+    assert!(suite.update(ALICE, "articles", "a1", r#"{"title":"edited"}"#).is_err());
+    // Overwriting with Set is held to the same override
+    assert!(suite.set(ALICE, "articles", "a1", r#"{"title":"replaced"}"#).is_err());
+    suite.update(ADMIN, "articles", "a1", r#"{"title":"edited"}"#).unwrap();
+}
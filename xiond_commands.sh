@@ -10,6 +10,10 @@ NODE=https://rpc.xion-testnet-2.burnt.com:443
 CHAIN_ID=xion-testnet-2
 KEY=<your-key>
 USER=<user-xion-address>
+KEY2=<second-admin-key>
+USER2=<second-admin-address>
+USER3=<third-admin-address>
+HOOK_CONTRACT=<hook-contract-address>
 
 # Set Document
 xiond tx wasm execute $CONTRACT '{"Set":{"collection":"mycol","document":"doc1","data":"{\"foo\":\"bar\"}"}}' \
@@ -35,10 +39,51 @@ xiond tx wasm execute $CONTRACT '{"GrantRole":{"user":"'$USER'","role":"editor"}
 xiond tx wasm execute $CONTRACT '{"RevokeRole":{"user":"'$USER'","role":"editor"}}' \
   --from $KEY --gas auto --gas-adjustment 1.3 --gas-prices 0.025uxion --broadcast-mode sync --chain-id $CHAIN_ID --node $NODE
 
-# Transfer Admin
-xiond tx wasm execute $CONTRACT '{"TransferAdmin":{"new_admin":"'$USER'"}}' \
+# Propose Admin (the new admin then sends AcceptAdmin)
+xiond tx wasm execute $CONTRACT '{"ProposeAdmin":{"new_admin":"'$USER'"}}' \
   --from $KEY --gas auto --gas-adjustment 1.3 --gas-prices 0.025uxion --broadcast-mode sync --chain-id $CHAIN_ID --node $NODE
 
+# Hand admin rights to a 2-of-3 admin set
+xiond tx wasm execute $CONTRACT '{"SetAdminSet":{"members":["'$USER'","'$USER2'","'$USER3'"],"threshold":2}}' \
+  --from $KEY --gas auto --gas-adjustment 1.3 --gas-prices 0.025uxion --broadcast-mode sync --chain-id $CHAIN_ID --node $NODE
+
+# Propose and approve a privileged action as admin set members
+xiond tx wasm execute $CONTRACT '{"ProposeAdminAction":{"msg":{"GrantRole":{"user":"'$USER'","role":"editor"}}}}' \
+  --from $KEY --gas auto --gas-adjustment 1.3 --gas-prices 0.025uxion --broadcast-mode sync --chain-id $CHAIN_ID --node $NODE
+xiond tx wasm execute $CONTRACT '{"ApproveAdminAction":{"action_id":1}}' \
+  --from $KEY2 --gas auto --gas-adjustment 1.3 --gas-prices 0.025uxion --broadcast-mode sync --chain-id $CHAIN_ID --node $NODE
+
+# Accept Admin
+xiond tx wasm execute $CONTRACT '{"AcceptAdmin":{}}' \
+  --from $KEY --gas auto --gas-adjustment 1.3 --gas-prices 0.025uxion --broadcast-mode sync --chain-id $CHAIN_ID --node $NODE
+
+# Set Collection Pricing (1000uxion per write plus 10uxion per byte)
+xiond tx wasm execute $CONTRACT '{"SetCollectionPricing":{"collection":"mycol","pricing":{"token":{"Native":"uxion"},"write_fee":"1000","byte_fee":"10","deposit":"0"}}}' \
+  --from $KEY --gas auto --gas-adjustment 1.3 --gas-prices 0.025uxion --broadcast-mode sync --chain-id $CHAIN_ID --node $NODE
+
+# Paid Set, funds must match the price exactly
+xiond tx wasm execute $CONTRACT '{"Set":{"collection":"mycol","document":"doc3","data":"{\"foo\":\"bar\"}"}}' \
+  --amount 1130uxion --from $KEY --gas auto --gas-adjustment 1.3 --gas-prices 0.025uxion --broadcast-mode sync --chain-id $CHAIN_ID --node $NODE
+
+# Notify a contract after every write to mycol, failures revert the write
+xiond tx wasm execute $CONTRACT '{"AddHook":{"collection":"mycol","contract":"'$HOOK_CONTRACT'","mode":"Required"}}' \
+  --from $KEY --gas auto --gas-adjustment 1.3 --gas-prices 0.025uxion --broadcast-mode sync --chain-id $CHAIN_ID --node $NODE
+
+# Pause all document writes
+xiond tx wasm execute $CONTRACT '{"Pause":{"scope":"Contract","reason":"investigating spam"}}' \
+  --from $KEY --gas auto --gas-adjustment 1.3 --gas-prices 0.025uxion --broadcast-mode sync --chain-id $CHAIN_ID --node $NODE
+
+# Unpause
+xiond tx wasm execute $CONTRACT '{"Unpause":{"scope":"Contract"}}' \
+  --from $KEY --gas auto --gas-adjustment 1.3 --gas-prices 0.025uxion --broadcast-mode sync --chain-id $CHAIN_ID --node $NODE
+
+# Rename a collection, repeat until the response has done=true
+xiond tx wasm execute $CONTRACT '{"RenameCollection":{"from":"posts_v1","to":"posts","limit":100}}' \
+  --from $KEY --gas auto --gas-adjustment 1.3 --gas-prices 0.025uxion --broadcast-mode sync --chain-id $CHAIN_ID --node $NODE
+
+# Export the first page of a snapshot, pass next_cursor, checksum and records_total back for the next page
+xiond query wasm contract-state smart $CONTRACT '{"Export":{"limit":50}}' --node $NODE
+
 # Batch Write
 xiond tx wasm execute $CONTRACT '{"BatchWrite":{"operations":[{"collection":"mycol","document":"doc2","operation":{"Set":{"data":"{\"foo\":\"bar2\"}"}}}]}}' \
   --from $KEY --gas auto --gas-adjustment 1.3 --gas-prices 0.025uxion --broadcast-mode sync --chain-id $CHAIN_ID --node $NODE
@@ -57,6 +102,15 @@ xiond query wasm contract-state smart $CONTRACT '{"UserDocuments":{"owner":"'$US
 # Get Collection Permissions
 xiond query wasm contract-state smart $CONTRACT '{"GetCollectionPermissions":{"collection":"mycol"}}' --node $NODE
 
+# Get Admin
+xiond query wasm contract-state smart $CONTRACT '{"GetAdmin":{}}' --node $NODE
+
+# List Hooks
+xiond query wasm contract-state smart $CONTRACT '{"ListHooks":{"collection":"mycol"}}' --node $NODE
+
+# List Pending Admin Actions
+xiond query wasm contract-state smart $CONTRACT '{"ListAdminActions":{"pending_only":true,"limit":10}}' --node $NODE
+
 # Get User Roles
 xiond query wasm contract-state smart $CONTRACT '{"GetUserRoles":{"user":"'$USER'"}}' --node $NODE
 